@@ -5,7 +5,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
-    time::Instant,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
 use rayon::prelude::*;
@@ -13,28 +13,39 @@ use serde::{Deserialize, Serialize};
 use tauri::{async_runtime, AppHandle, Wry};
 
 use crate::{
+    catalog::{catalog_album_media, catalog_albums, schedule_catalog_refresh},
     constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
     duplicates::compute_hash_for_path,
+    library::{ensure_online, known_library_root, touch_library},
     metadata::{
-        get_file_metadata_cached, get_metadata_with_favorite, read_album_meta,
+        copy_embedded_timestamp, copy_media_metadata_batch, get_file_metadata_cached,
+        get_metadata_with_favorite, merge_album_meta_caller_holds_lock, read_album_meta,
         transfer_media_metadata_batch_caller_holds_lock,
-        transfer_media_metadata_entry_caller_holds_lock, DetachedAlbum, DetachedMediaEntry,
+        transfer_media_metadata_entry_caller_holds_lock, AlbumProperties, DetachedAlbum,
+        DetachedMediaEntry, FileMetaEntry,
     },
     preload::{
-        artifacts_missing, drop_preload_for_path, enqueue_preload, preload_dir, set_active_root,
-        start_preloader_worker, CURRENT_PRELOAD_CANCEL, PRELOADED, PRELOAD_QUEUE,
+        artifacts_missing, drop_preload_for_path, enqueue_preload, mark_album_preloaded,
+        preload_dir, start_preloader_worker, switch_root, CURRENT_PRELOAD_CANCEL, PRELOADED,
+        PRELOAD_QUEUE,
+    },
+    settings::{read_settings, read_settings_for, CollisionPolicy},
+    smart_albums::{
+        detached_smart_albums, parse_smart_album_id, rename_smart_album, smart_album_path,
     },
-    settings::read_settings,
     thumb::{ensure_thumb, ensure_thumb_with_settings},
-    util::{has_extension, STORE_WRITE_LOCK},
+    util::{clone_file, file_digest, has_extension, STORE_WRITE_LOCK},
 };
 
+const TRASH_DIR: &str = ".room237-trash";
+const TRASH_ORIGIN_EXT: &str = "origin";
+
 #[derive(Clone)]
-struct AlbumDirEntry {
-    path: PathBuf,
-    name: String,
-    relative_path: String,
-    parent: Option<String>,
+pub(crate) struct AlbumDirEntry {
+    pub(crate) path: PathBuf,
+    pub(crate) name: String,
+    pub(crate) relative_path: String,
+    pub(crate) parent: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -50,39 +61,22 @@ pub struct RenamedAlbumResult {
 }
 
 fn unique_filename(dir: &Path, file_name: &str) -> String {
-    let path = dir.join(file_name);
-    let stem_raw = Path::new(file_name)
+    if !dir.join(file_name).exists() {
+        return file_name.to_string();
+    }
+    let stem = Path::new(file_name)
         .file_stem()
         .and_then(|s| s.to_str())
-        .unwrap_or(file_name)
-        .to_string();
+        .unwrap_or(file_name);
     let ext = Path::new(file_name)
         .extension()
         .and_then(|s| s.to_str())
         .map(|s| format!(".{}", s))
         .unwrap_or_default();
 
-    let (base_stem, start_counter) = if let Some(pos) = stem_raw.rfind('_') {
-        if let Ok(n) = stem_raw[pos + 1..].parse::<usize>() {
-            (stem_raw[..pos].to_string(), n)
-        } else {
-            (stem_raw.clone(), 0)
-        }
-    } else {
-        (stem_raw.clone(), 0)
-    };
-
-    if !path.exists() && start_counter == 0 {
-        return file_name.to_string();
-    }
-
-    let mut counter = if start_counter == 0 {
-        1
-    } else {
-        start_counter + 1
-    };
+    let mut counter = 1;
     loop {
-        let candidate = format!("{}_{}{}", base_stem, counter, ext);
+        let candidate = format!("{}_{}{}", stem, counter, ext);
         if !dir.join(&candidate).exists() {
             return candidate;
         }
@@ -90,6 +84,42 @@ fn unique_filename(dir: &Path, file_name: &str) -> String {
     }
 }
 
+pub(crate) enum Placement {
+    Free(String),
+    Renamed(String),
+    Replace(String),
+    Skip,
+    Conflict,
+}
+
+pub(crate) fn place_in_dir(
+    source: &Path,
+    dir: &Path,
+    name: &str,
+    policy: CollisionPolicy,
+) -> Result<Placement, String> {
+    let existing = dir.join(name);
+    if !existing.exists() {
+        return Ok(Placement::Free(name.to_string()));
+    }
+    match policy {
+        CollisionPolicy::Suffix => Ok(Placement::Renamed(unique_filename(dir, name))),
+        CollisionPolicy::Skip => Ok(Placement::Skip),
+        CollisionPolicy::Ask => Ok(Placement::Conflict),
+        CollisionPolicy::OverwriteIfIdentical => {
+            let same_size = match (source.metadata(), existing.metadata()) {
+                (Ok(a), Ok(b)) => a.is_file() && b.is_file() && a.len() == b.len(),
+                _ => false,
+            };
+            if same_size && file_digest(source)? == file_digest(&existing)? {
+                Ok(Placement::Replace(name.to_string()))
+            } else {
+                Ok(Placement::Renamed(unique_filename(dir, name)))
+            }
+        }
+    }
+}
+
 fn is_album_dir(path: &Path) -> bool {
     path.is_dir()
         && path
@@ -99,7 +129,7 @@ fn is_album_dir(path: &Path) -> bool {
             .unwrap_or(false)
 }
 
-fn normalized_relative_path(path: &Path) -> String {
+pub(crate) fn normalized_relative_path(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
 }
 
@@ -127,7 +157,7 @@ pub(crate) fn walk_album_paths(root: &Path) -> Result<Vec<PathBuf>, String> {
     Ok(albums)
 }
 
-fn walk_album_entries(root: &Path) -> Result<Vec<AlbumDirEntry>, String> {
+pub(crate) fn walk_album_entries(root: &Path) -> Result<Vec<AlbumDirEntry>, String> {
     let mut entries = Vec::new();
     let albums = walk_album_paths(root)?;
 
@@ -162,7 +192,7 @@ fn walk_album_entries(root: &Path) -> Result<Vec<AlbumDirEntry>, String> {
     Ok(entries)
 }
 
-fn media_files_for_album(path: &Path) -> Result<Vec<PathBuf>, String> {
+pub(crate) fn media_files_for_album(path: &Path) -> Result<Vec<PathBuf>, String> {
     let files = fs::read_dir(path)
         .map_err(|e| e.to_string())?
         .filter_map(|e| e.ok())
@@ -180,43 +210,11 @@ fn media_files_for_album(path: &Path) -> Result<Vec<PathBuf>, String> {
     Ok(files)
 }
 
-fn next_available_name(dir: &Path, name: &str) -> Result<String, String> {
-    let ext = Path::new(name)
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("");
-    let stem_raw = Path::new(name)
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("file");
-    let (base, start_counter) = if let Some(pos) = stem_raw.rfind('_') {
-        if let Ok(n) = stem_raw[pos + 1..].parse::<usize>() {
-            (stem_raw[..pos].to_string(), n)
-        } else {
-            (stem_raw.to_string(), 0)
-        }
-    } else {
-        (stem_raw.to_string(), 0)
-    };
-    let mut counter = start_counter;
-    loop {
-        let candidate = if counter == 0 {
-            if ext.is_empty() {
-                base.clone()
-            } else {
-                format!("{base}.{ext}")
-            }
-        } else if ext.is_empty() {
-            format!("{base}_{counter}")
-        } else {
-            format!("{base}_{counter}.{ext}")
-        };
-        let candidate_path = dir.join(&candidate);
-        if !candidate_path.exists() {
-            return Ok(candidate);
-        }
-        counter += 1;
+pub(crate) fn next_available_name(dir: &Path, name: &str) -> Result<String, String> {
+    if name.is_empty() {
+        return Err("File name cannot be empty".to_string());
     }
+    Ok(unique_filename(dir, name))
 }
 
 #[derive(Serialize)]
@@ -230,6 +228,52 @@ pub struct FavoriteDetachedMediaEntry {
     pub favorite: bool,
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryMediaEntry {
+    pub meta: String,
+    pub name: String,
+    pub album_path: String,
+    pub album_name: String,
+    pub album_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favorite: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumb: Option<String>,
+}
+
+pub(crate) fn library_media_entry(
+    album: &AlbumDirEntry,
+    name: &str,
+    entry: &FileMetaEntry,
+) -> Option<LibraryMediaEntry> {
+    let meta = match entry.meta.clone() {
+        Some(meta) => meta,
+        None => {
+            let media_path = album.path.join(name);
+            match get_metadata_with_favorite(&media_path) {
+                Ok(m) => m.meta,
+                Err(e) => {
+                    log::warn!("failed to read metadata {} {}", media_path.display(), e);
+                    return None;
+                }
+            }
+        }
+    };
+    Some(LibraryMediaEntry {
+        meta,
+        name: name.to_string(),
+        album_path: album.path.to_string_lossy().to_string(),
+        album_name: album.relative_path.clone(),
+        album_id: album.relative_path.clone(),
+        favorite: if entry.favorite { Some(true) } else { None },
+        tags: entry.tags.clone(),
+        thumb: None,
+    })
+}
+
 #[derive(Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingFile {
@@ -247,9 +291,17 @@ pub fn get_albums_detached(
 ) -> Result<Vec<DetachedAlbum>, String> {
     let root = PathBuf::from(&root_dir);
     if !root.is_dir() {
-        return Err(format!("{} is not a directory", root.display()));
+        let albums = catalog_albums(&root)
+            .ok_or_else(|| format!("{} is not a directory", root.display()))?;
+        log::info!(
+            "library {} is offline, listed {} albums from catalog",
+            root.display(),
+            albums.len()
+        );
+        return Ok(albums);
     }
-    set_active_root(&root);
+    switch_root(&root);
+    touch_library(&root);
 
     let mut albums = Vec::new();
     let entries = walk_album_entries(&root)?;
@@ -269,9 +321,21 @@ pub fn get_albums_detached(
             .collect();
 
         let media_files = &media_by_entry[idx];
+        let properties = read_album_meta(&entry.path).properties;
+        let cover = properties
+            .cover
+            .as_ref()
+            .map(|name| entry.path.join(name))
+            .filter(|p| p.is_file());
 
         let files = media_files.len();
-        let thumb = if !thumb_files.is_empty() {
+        let thumb = if let Some(cover) = cover {
+            Some(
+                ensure_thumb(&cover, &thumb_dir)?
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        } else if !thumb_files.is_empty() {
             Some(thumb_files[0].to_string_lossy().into_owned())
         } else if let Some(first) = media_files.first() {
             Some(
@@ -310,14 +374,19 @@ pub fn get_albums_detached(
             size: files,
             relative_path: entry.relative_path.clone(),
             parent: entry.parent.clone(),
+            smart: None,
+            properties,
+            offline: None,
         });
 
         enqueue_preload(&entry.path);
     }
 
     start_preloader_worker(app.clone());
+    schedule_catalog_refresh(&root);
 
     albums.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    albums.extend(detached_smart_albums(&root));
     log::info!("listed {} albums", albums.len());
     Ok(albums)
 }
@@ -332,7 +401,9 @@ pub async fn get_album_media(
         let t_start = Instant::now();
         let dir = PathBuf::from(&dir);
         if !dir.is_dir() {
-            return Err(format!("{} is not a directory", dir.display()));
+            return known_library_root(&dir)
+                .and_then(|root| catalog_album_media(&root, &dir))
+                .ok_or_else(|| format!("{} is not a directory", dir.display()));
         }
 
         let mut preloaded = PRELOADED.lock().unwrap();
@@ -348,7 +419,7 @@ pub async fn get_album_media(
                 log::info!("preload cancelled for {}", dir.display());
             }
             let _ = preload_dir(&dir, Arc::new(AtomicBool::new(false)), false);
-            PRELOADED.lock().unwrap().insert(dir.clone());
+            mark_album_preloaded(&dir);
             {
                 let mut q = PRELOAD_QUEUE.lock().unwrap();
                 q.retain(|p| p != &dir);
@@ -385,6 +456,8 @@ pub async fn get_album_media(
                         meta: meta_str,
                         name: name.clone(),
                         favorite: if entry.favorite { Some(true) } else { None },
+                        tags: entry.tags.clone(),
+                        thumb: None,
                     });
                     used_cached = true;
                 }
@@ -422,6 +495,12 @@ pub async fn get_album_media(
                         .get(&name)
                         .map(|e| if e.favorite { Some(true) } else { None })
                         .unwrap_or(None),
+                    tags: album_meta
+                        .files
+                        .get(&name)
+                        .map(|e| e.tags.clone())
+                        .unwrap_or_default(),
+                    thumb: None,
                 });
             }
         }
@@ -514,6 +593,18 @@ pub fn rename_album(
     if album_id.eq_ignore_ascii_case("favorites") {
         return Err("Cannot rename favorites album".to_string());
     }
+    if let Some(smart_id) = parse_smart_album_id(&album_id) {
+        let renamed = rename_smart_album(Path::new(&root_dir), smart_id, &new_name)?;
+        let path = smart_album_path(&renamed.id);
+        return Ok(RenamedAlbumResult {
+            old_path: path.clone(),
+            new_path: path.clone(),
+            old_relative_path: path.clone(),
+            new_relative_path: path,
+            parent: None,
+            name: renamed.name,
+        });
+    }
     let trimmed = new_name.trim();
     if trimmed.is_empty() {
         return Err("Album name cannot be empty".to_string());
@@ -523,6 +614,7 @@ pub fn rename_album(
     let cleanup_delay = settings.album.rename_cleanup_delay_secs;
 
     let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
     if !root.is_dir() {
         return Err(format!("{} is not a directory", root.display()));
     }
@@ -644,8 +736,12 @@ pub fn move_album(
     if album_id.eq_ignore_ascii_case("favorites") {
         return Err("Cannot move favorites album".to_string());
     }
+    if parse_smart_album_id(&album_id).is_some() {
+        return Err("Cannot move smart album".to_string());
+    }
 
     let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
     if !root.is_dir() {
         return Err(format!("{} is not a directory", root.display()));
     }
@@ -771,82 +867,142 @@ pub fn get_album_size(dir: String) -> Result<u64, String> {
     Ok(total)
 }
 
-fn move_one_media(
-    source_dir: &PathBuf,
-    target_dir: &PathBuf,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MoveStatus {
+    Moved,
+    Renamed,
+    Replaced,
+    Skipped,
+    Conflict,
+    Failed,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveOutcome {
+    pub name: String,
+    pub status: MoveStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl MoveOutcome {
+    fn failed(name: &str, error: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: MoveStatus::Failed,
+            target: None,
+            error: Some(error),
+        }
+    }
+}
+
+struct PlannedMove {
+    media: String,
+    source_name: String,
+    target_name: String,
+    status: MoveStatus,
+}
+
+impl PlannedMove {
+    fn outcome(self) -> MoveOutcome {
+        MoveOutcome {
+            name: self.media,
+            status: self.status,
+            target: Some(self.target_name),
+            error: None,
+        }
+    }
+}
+
+fn plan_move(
+    source_dir: &Path,
+    target_dir: &Path,
     media: &str,
-    move_artifacts: bool,
-) -> Result<(), String> {
-    let media_name = PathBuf::from(media);
-    let source_file = source_dir.join(&media_name);
-    let source_name = media_name
+    policy: CollisionPolicy,
+) -> Result<PlannedMove, MoveOutcome> {
+    let source_name = Path::new(media)
         .file_name()
         .and_then(|s| s.to_str())
-        .unwrap_or("file");
-    let target_name = unique_filename(target_dir, source_name);
-    let target_file = target_dir.join(&target_name);
-
+        .ok_or_else(|| MoveOutcome::failed(media, "Invalid file name".to_string()))?
+        .to_string();
+    let source_file = source_dir.join(&source_name);
     if !source_file.is_file() {
-        return Err("file clash".into());
+        return Err(MoveOutcome::failed(media, "File not found".to_string()));
+    }
+    let (target_name, status) = match place_in_dir(&source_file, target_dir, &source_name, policy)
+        .map_err(|e| MoveOutcome::failed(media, e))?
+    {
+        Placement::Free(name) => (name, MoveStatus::Moved),
+        Placement::Renamed(name) => (name, MoveStatus::Renamed),
+        Placement::Replace(name) => (name, MoveStatus::Replaced),
+        Placement::Skip => (source_name.clone(), MoveStatus::Skipped),
+        Placement::Conflict => (source_name.clone(), MoveStatus::Conflict),
+    };
+    let planned = PlannedMove {
+        media: media.to_string(),
+        source_name,
+        target_name,
+        status,
+    };
+    if matches!(status, MoveStatus::Skipped | MoveStatus::Conflict) {
+        return Err(planned.outcome());
     }
+    Ok(planned)
+}
 
-    let (src_thumb_dir, src_meta_dir) = (
-        source_dir.join(".room237-thumb"),
-        source_dir.join(".room237-metadata"),
-    );
-    let (tgt_thumb_dir, tgt_meta_dir) = (
-        target_dir.join(".room237-thumb"),
-        target_dir.join(".room237-metadata"),
-    );
+fn move_one_media(
+    source_dir: &Path,
+    target_dir: &Path,
+    media: &str,
+    move_artifacts: bool,
+    policy: CollisionPolicy,
+) -> MoveOutcome {
+    let plan = match plan_move(source_dir, target_dir, media, policy) {
+        Ok(plan) => plan,
+        Err(outcome) => return outcome,
+    };
+    let source_file = source_dir.join(&plan.source_name);
+    let target_file = target_dir.join(&plan.target_name);
 
     if move_artifacts {
         let _guard = STORE_WRITE_LOCK.lock().unwrap();
 
-        transfer_media_metadata_entry_caller_holds_lock(
-            source_dir.as_path(),
-            source_name,
-            target_dir.as_path(),
-            &target_name,
-        )?;
-
-        let thumb_name = {
-            let mut os = media_name.file_name().unwrap().to_os_string();
-            os.push(".webp");
-            PathBuf::from(os)
-        };
-        if src_thumb_dir.join(&thumb_name).exists() {
-            fs::create_dir_all(&tgt_thumb_dir).map_err(|e| e.to_string())?;
-            let mut tgt_name = thumb_name.clone();
-            tgt_name.set_file_name(format!("{}.webp", target_name));
-            let _ = fs::rename(
-                src_thumb_dir.join(&thumb_name),
-                tgt_thumb_dir.join(&tgt_name),
-            );
+        if let Err(e) = transfer_media_metadata_entry_caller_holds_lock(
+            source_dir,
+            &plan.source_name,
+            target_dir,
+            &plan.target_name,
+        ) {
+            return MoveOutcome::failed(media, e);
         }
-
-        let meta_name = {
-            let mut os = media_name.file_name().unwrap().to_os_string();
-            os.push(".meta");
-            PathBuf::from(os)
-        };
-        if src_meta_dir.join(&meta_name).exists() {
-            fs::create_dir_all(&tgt_meta_dir).map_err(|e| e.to_string())?;
-            let mut tgt_meta = meta_name.clone();
-            tgt_meta.set_file_name(format!("{}.meta", target_name));
-            let _ = fs::rename(src_meta_dir.join(&meta_name), tgt_meta_dir.join(&tgt_meta));
+        move_media_artifacts(source_dir, &plan.source_name, target_dir, &plan.target_name);
+
+        if let Err(e) = fs::rename(&source_file, &target_file) {
+            move_media_artifacts(target_dir, &plan.target_name, source_dir, &plan.source_name);
+            let _ = transfer_media_metadata_entry_caller_holds_lock(
+                target_dir,
+                &plan.target_name,
+                source_dir,
+                &plan.source_name,
+            );
+            return MoveOutcome::failed(media, e.to_string());
         }
-
-        fs::rename(&source_file, &target_file).map_err(|e| e.to_string())?;
     } else {
         log::info!(
             "Skipped moving thumbnails/metadata for {} due to settings",
             media
         );
-        fs::rename(&source_file, &target_file).map_err(|e| e.to_string())?;
+        if let Err(e) = fs::rename(&source_file, &target_file) {
+            return MoveOutcome::failed(media, e.to_string());
+        }
     }
 
     log::info!("move {} → {}", source_file.display(), target_file.display());
-    Ok(())
+    plan.outcome()
 }
 
 #[tauri::command]
@@ -856,10 +1012,27 @@ pub fn move_media(source: String, target: String, media: String) -> Result<Strin
     let settings = read_settings();
     let move_artifacts = settings.album.move_rename_thumbs_and_meta;
 
+    ensure_online(&source_dir)?;
+    ensure_online(&target_dir)?;
     if !(source_dir.is_dir() && target_dir.is_dir()) {
         return Err("bad dirs".into());
     }
-    move_one_media(&source_dir, &target_dir, &media, move_artifacts).map(|()| "ok".into())
+    let outcome = move_one_media(
+        &source_dir,
+        &target_dir,
+        &media,
+        move_artifacts,
+        settings.album.collision_policy,
+    );
+    match outcome.status {
+        MoveStatus::Failed => Err(outcome.error.unwrap_or_default()),
+        MoveStatus::Skipped | MoveStatus::Conflict => Err(format!(
+            "\"{}\" already exists in {}",
+            media,
+            target_dir.display()
+        )),
+        _ => Ok("ok".into()),
+    }
 }
 
 fn move_media_batch_blocking(
@@ -867,125 +1040,694 @@ fn move_media_batch_blocking(
     target_dir: PathBuf,
     media: Vec<String>,
     move_artifacts: bool,
-) -> Result<Vec<String>, String> {
+    policy: CollisionPolicy,
+) -> Result<Vec<MoveOutcome>, String> {
     if !(source_dir.is_dir() && target_dir.is_dir()) {
         return Err("bad dirs".into());
     }
 
-    if move_artifacts && !media.is_empty() {
-        let mut moves: Vec<(String, String)> = Vec::with_capacity(media.len());
-        for name in &media {
-            let media_name = PathBuf::from(name);
-            let source_name = media_name
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("file");
-            let target_name = unique_filename(&target_dir, source_name);
-            moves.push((source_name.to_string(), target_name));
+    if !move_artifacts {
+        return Ok(media
+            .iter()
+            .map(|name| move_one_media(&source_dir, &target_dir, name, false, policy))
+            .collect());
+    }
+
+    let mut outcomes = Vec::with_capacity(media.len());
+    let mut moves: Vec<(String, String)> = Vec::with_capacity(media.len());
+
+    let _guard = STORE_WRITE_LOCK.lock().unwrap();
+    for name in &media {
+        let plan = match plan_move(&source_dir, &target_dir, name, policy) {
+            Ok(plan) => plan,
+            Err(outcome) => {
+                outcomes.push(outcome);
+                continue;
+            }
+        };
+        let source_file = source_dir.join(&plan.source_name);
+        let target_file = target_dir.join(&plan.target_name);
+        if let Err(e) = fs::rename(&source_file, &target_file) {
+            log::warn!(
+                "move_media_batch: rename failed {} → {}: {}",
+                source_file.display(),
+                target_file.display(),
+                e
+            );
+            outcomes.push(MoveOutcome::failed(name, e.to_string()));
+            continue;
         }
+        move_media_artifacts(
+            &source_dir,
+            &plan.source_name,
+            &target_dir,
+            &plan.target_name,
+        );
+        moves.push((plan.source_name.clone(), plan.target_name.clone()));
+        outcomes.push(plan.outcome());
+    }
 
-        let src_thumb = source_dir.join(".room237-thumb");
-        let tgt_thumb = target_dir.join(".room237-thumb");
-        let src_meta_dir = source_dir.join(".room237-metadata");
-        let tgt_meta_dir = target_dir.join(".room237-metadata");
+    if let Err(e) =
+        transfer_media_metadata_batch_caller_holds_lock(&source_dir, &target_dir, &moves)
+    {
+        log::error!(
+            "move_media_batch: failed to transfer metadata {} → {}: {}",
+            source_dir.display(),
+            target_dir.display(),
+            e
+        );
+    }
+    Ok(outcomes)
+}
 
-        let _guard = STORE_WRITE_LOCK.lock().unwrap();
-        transfer_media_metadata_batch_caller_holds_lock(&source_dir, &target_dir, &moves)?;
+#[tauri::command]
+pub async fn move_media_batch(
+    source: String,
+    target: String,
+    media: Vec<String>,
+    collision_policy: Option<CollisionPolicy>,
+) -> Result<Vec<MoveOutcome>, String> {
+    let source_dir = PathBuf::from(&source);
+    let target_dir = PathBuf::from(&target);
+    ensure_online(&source_dir)?;
+    ensure_online(&target_dir)?;
+    let settings = read_settings();
+    let move_artifacts = settings.album.move_rename_thumbs_and_meta;
+    let policy = collision_policy.unwrap_or(settings.album.collision_policy);
 
-        let mut failed = Vec::new();
-        for (i, name) in media.iter().enumerate() {
-            let (_source_name, target_name) = &moves[i];
-            let media_name = PathBuf::from(name);
-            let source_file = source_dir.join(&media_name);
-            let target_file = target_dir.join(&target_name);
+    async_runtime::spawn_blocking(move || {
+        move_media_batch_blocking(source_dir, target_dir, media, move_artifacts, policy)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-            if !source_file.is_file() {
-                failed.push(name.clone());
-                continue;
-            }
+fn copy_media_batch_blocking(
+    source_dir: PathBuf,
+    target_dir: PathBuf,
+    media: Vec<String>,
+    copy_artifacts: bool,
+) -> Result<Vec<String>, String> {
+    if !(source_dir.is_dir() && target_dir.is_dir()) {
+        return Err("bad dirs".into());
+    }
 
-            let thumb_name = {
-                let mut os = media_name.file_name().unwrap().to_os_string();
-                os.push(".webp");
-                PathBuf::from(os)
-            };
-            if src_thumb.join(&thumb_name).exists() {
+    let src_thumb = source_dir.join(".room237-thumb");
+    let tgt_thumb = target_dir.join(".room237-thumb");
+    let src_meta_dir = source_dir.join(".room237-metadata");
+    let tgt_meta_dir = target_dir.join(".room237-metadata");
+
+    let mut failed = Vec::new();
+    let mut copies: Vec<(String, String)> = Vec::with_capacity(media.len());
+    for name in media {
+        let Some(source_name) = Path::new(&name)
+            .file_name()
+            .and_then(|s| s.to_str())
+            .map(str::to_string)
+        else {
+            failed.push(name);
+            continue;
+        };
+        let source_file = source_dir.join(&source_name);
+        if !source_file.is_file() {
+            failed.push(name);
+            continue;
+        }
+        let target_name = unique_filename(&target_dir, &source_name);
+        let target_file = target_dir.join(&target_name);
+        if let Err(e) = clone_file(&source_file, &target_file) {
+            log::warn!("copy_media_batch: {}", e);
+            failed.push(name);
+            continue;
+        }
+        copy_embedded_timestamp(&source_file, &target_file);
+
+        if copy_artifacts {
+            let thumb = src_thumb.join(format!("{}.webp", source_name));
+            if thumb.exists() {
                 let _ = fs::create_dir_all(&tgt_thumb);
-                let mut tgt_name = thumb_name.clone();
-                tgt_name.set_file_name(format!("{}.webp", target_name));
-                let _ = fs::rename(src_thumb.join(&thumb_name), tgt_thumb.join(&tgt_name));
+                let _ = clone_file(&thumb, &tgt_thumb.join(format!("{}.webp", target_name)));
             }
-
-            let meta_name = {
-                let mut os = media_name.file_name().unwrap().to_os_string();
-                os.push(".meta");
-                PathBuf::from(os)
-            };
-            if src_meta_dir.join(&meta_name).exists() {
+            let meta = src_meta_dir.join(format!("{}.meta", source_name));
+            if meta.exists() {
                 let _ = fs::create_dir_all(&tgt_meta_dir);
-                let mut tgt_meta = meta_name.clone();
-                tgt_meta.set_file_name(format!("{}.meta", target_name));
-                let _ = fs::rename(src_meta_dir.join(&meta_name), tgt_meta_dir.join(&tgt_meta));
-            }
-
-            if let Err(e) = fs::rename(&source_file, &target_file) {
-                log::warn!(
-                    "move_media_batch: rename failed {} → {}: {}",
-                    source_file.display(),
-                    target_file.display(),
-                    e
-                );
-                failed.push(name.clone());
+                let _ = clone_file(&meta, &tgt_meta_dir.join(format!("{}.meta", target_name)));
             }
         }
+        log::info!("copy {} → {}", source_file.display(), target_file.display());
+        copies.push((source_name, target_name));
+    }
 
-        if !failed.is_empty() {
-            let rollback: Vec<(String, String)> = failed
-                .iter()
-                .filter_map(|name| {
-                    let i = media.iter().position(|m| m == name)?;
-                    let (src, tgt) = moves.get(i)?;
-                    Some((tgt.clone(), src.clone()))
-                })
-                .collect();
-            let _ = transfer_media_metadata_batch_caller_holds_lock(
-                &target_dir,
-                &source_dir,
-                &rollback,
-            );
-        }
-        Ok(failed)
+    if copy_artifacts {
+        copy_media_metadata_batch(&source_dir, &target_dir, &copies)?;
     } else {
-        let failed: Vec<String> = media
-            .into_iter()
-            .filter_map(|name| {
-                move_one_media(&source_dir, &target_dir, &name, move_artifacts)
-                    .err()
-                    .map(|_| name)
-            })
-            .collect();
-        Ok(failed)
+        log::info!("Skipped copying thumbnails/metadata due to settings");
+        if !copies.is_empty() {
+            enqueue_preload(&target_dir);
+        }
     }
+    Ok(failed)
 }
 
 #[tauri::command]
-pub async fn move_media_batch(
+pub async fn copy_media_batch(
     source: String,
     target: String,
     media: Vec<String>,
 ) -> Result<Vec<String>, String> {
     let source_dir = PathBuf::from(&source);
     let target_dir = PathBuf::from(&target);
-    let settings = read_settings();
-    let move_artifacts = settings.album.move_rename_thumbs_and_meta;
+    ensure_online(&source_dir)?;
+    ensure_online(&target_dir)?;
+    let copy_artifacts = read_settings().album.move_rename_thumbs_and_meta;
+
+    async_runtime::spawn_blocking(move || {
+        copy_media_batch_blocking(source_dir, target_dir, media, copy_artifacts)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn copy_album_tree(source: &Path, target: &Path) -> Result<(), String> {
+    fs::create_dir(target).map_err(|e| format!("{}: {}", target.display(), e))?;
+    for entry in fs::read_dir(source).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let file_type = entry.file_type().map_err(|e| e.to_string())?;
+        let path = entry.path();
+        let dest = target.join(entry.file_name());
+        if file_type.is_dir() {
+            if entry.file_name() == ".room237-upload" {
+                continue;
+            }
+            copy_album_tree(&path, &dest)?;
+        } else if file_type.is_file() {
+            clone_file(&path, &dest)?;
+            copy_embedded_timestamp(&path, &dest);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn copy_album(
+    root_dir: String,
+    album_id: String,
+    new_parent_id: Option<String>,
+) -> Result<RenamedAlbumResult, String> {
+    if album_id.trim().is_empty() {
+        return Err("Album id is required".to_string());
+    }
+    if album_id.eq_ignore_ascii_case("favorites") {
+        return Err("Cannot copy favorites album".to_string());
+    }
+    if parse_smart_album_id(&album_id).is_some() {
+        return Err("Cannot copy smart album".to_string());
+    }
+
+    let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    let normalized_root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve root: {e}"))?;
+    let normalized_source = normalized_root
+        .join(Path::new(&album_id))
+        .canonicalize()
+        .map_err(|_| "Album not found".to_string())?;
+    if !normalized_source.starts_with(&normalized_root) || normalized_source == normalized_root {
+        return Err("Album path escapes root".to_string());
+    }
+    let album_name = normalized_source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid album name")?
+        .to_string();
+
+    let normalized_parent = new_parent_id
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| normalized_root.join(Path::new(&p)))
+        .unwrap_or(normalized_root.clone())
+        .canonicalize()
+        .map_err(|_| "Target album not found".to_string())?;
+    if !normalized_parent.is_dir() {
+        return Err("Target album is not a directory".to_string());
+    }
+    if !normalized_parent.starts_with(&normalized_root) {
+        return Err("Target path escapes root".to_string());
+    }
+    if normalized_parent.starts_with(&normalized_source) {
+        return Err("Cannot copy album into its own subtree".to_string());
+    }
 
-    let failed = async_runtime::spawn_blocking(move || {
-        move_media_batch_blocking(source_dir, target_dir, media, move_artifacts)
+    let mut name = album_name.clone();
+    let mut counter = 1;
+    while normalized_parent.join(&name).exists() {
+        name = if counter == 1 {
+            format!("{} copy", album_name)
+        } else {
+            format!("{} copy {}", album_name, counter)
+        };
+        counter += 1;
+    }
+    let new_path = normalized_parent.join(&name);
+
+    let (source, target) = (normalized_source.clone(), new_path.clone());
+    let started = Instant::now();
+    async_runtime::spawn_blocking(move || {
+        copy_album_tree(&source, &target).inspect_err(|_| {
+            let _ = fs::remove_dir_all(&target);
+        })
     })
     .await
     .map_err(|e| e.to_string())??;
+    log::info!(
+        "copy album {} → {} in {:?}",
+        normalized_source.display(),
+        new_path.display(),
+        started.elapsed()
+    );
 
-    Ok(failed)
+    let relative = |p: &Path| {
+        p.strip_prefix(&normalized_root)
+            .map_err(|e| e.to_string())
+            .map(normalized_relative_path)
+    };
+    Ok(RenamedAlbumResult {
+        old_path: normalized_source.to_string_lossy().to_string(),
+        new_path: new_path.to_string_lossy().to_string(),
+        old_relative_path: relative(&normalized_source)?,
+        new_relative_path: relative(&new_path)?,
+        parent: if normalized_parent == normalized_root {
+            None
+        } else {
+            relative(&normalized_parent).ok()
+        },
+        name,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeAlbumsResult {
+    pub target_path: String,
+    pub moved: usize,
+    pub skipped: Vec<String>,
+    pub failed: Vec<String>,
+    pub left_behind: Vec<String>,
+    pub trashed_path: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedAlbum {
+    pub id: String,
+    pub name: String,
+    pub original_path: Option<String>,
+    pub trashed_at: u64,
+}
+
+fn resolve_album_dir(root: &Path, album_id: &str) -> Result<PathBuf, String> {
+    if album_id.trim().is_empty() {
+        return Err("Album id is required".to_string());
+    }
+    if album_id.eq_ignore_ascii_case("favorites") || parse_smart_album_id(album_id).is_some() {
+        return Err(format!("\"{}\" is not a folder album", album_id));
+    }
+    let dir = root
+        .join(Path::new(album_id))
+        .canonicalize()
+        .map_err(|_| "Album not found".to_string())?;
+    if !dir.starts_with(root) || dir == root {
+        return Err("Album path escapes root".to_string());
+    }
+    if !is_album_dir(&dir) {
+        return Err("Album not found".to_string());
+    }
+    Ok(dir)
+}
+
+fn canonical_root(root_dir: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(root_dir);
+    ensure_online(&root)?;
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    root.canonicalize()
+        .map_err(|e| format!("Failed to resolve root: {e}"))
+}
+
+fn trash_dir(root: &Path) -> PathBuf {
+    root.join(TRASH_DIR)
+}
+
+fn trash_origin_file(trash: &Path, id: &str) -> PathBuf {
+    trash.join(format!("{id}.{TRASH_ORIGIN_EXT}"))
+}
+
+/// Trash entries are named `{millis}[.{n}]-{album name}`.
+fn parse_trash_id(id: &str) -> Option<(u64, &str)> {
+    let (stamp, name) = id.split_once('-')?;
+    let millis = stamp.split('.').next()?.parse().ok()?;
+    Some((millis, name))
+}
+
+fn move_to_trash(root: &Path, dir: &Path) -> Result<PathBuf, String> {
+    let trash = trash_dir(root);
+    fs::create_dir_all(&trash).map_err(|e| e.to_string())?;
+    let name = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid album name")?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let mut id = format!("{stamp}-{name}");
+    let mut counter = 2;
+    while trash.join(&id).exists() {
+        id = format!("{stamp}.{counter}-{name}");
+        counter += 1;
+    }
+    let dest = trash.join(&id);
+    fs::rename(dir, &dest).map_err(|e| e.to_string())?;
+    if let Ok(relative) = dir.strip_prefix(root) {
+        let _ = fs::write(
+            trash_origin_file(&trash, &id),
+            normalized_relative_path(relative),
+        );
+    }
+    drop_preload_for_path(dir);
+    log::info!("trashed {} → {}", dir.display(), dest.display());
+    Ok(dest)
+}
+
+fn resolve_trash_entry(root: &Path, id: &str) -> Result<PathBuf, String> {
+    if id.contains(['/', '\\']) || parse_trash_id(id).is_none() {
+        return Err(format!("Invalid trash entry \"{}\"", id));
+    }
+    let entry = trash_dir(root).join(id);
+    if !entry.is_dir() {
+        return Err(format!("Trash entry \"{}\" not found", id));
+    }
+    Ok(entry)
+}
+
+#[tauri::command]
+pub fn list_trash(root_dir: String) -> Result<Vec<TrashedAlbum>, String> {
+    let root = canonical_root(&root_dir)?;
+    let trash = trash_dir(&root);
+    let Ok(entries) = fs::read_dir(&trash) else {
+        return Ok(Vec::new());
+    };
+    let mut trashed: Vec<TrashedAlbum> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let id = e.file_name().to_string_lossy().into_owned();
+            let (trashed_at, name) = parse_trash_id(&id)?;
+            Some(TrashedAlbum {
+                name: name.to_string(),
+                original_path: fs::read_to_string(trash_origin_file(&trash, &id)).ok(),
+                trashed_at,
+                id,
+            })
+        })
+        .collect();
+    trashed.sort_by_key(|t| std::cmp::Reverse(t.trashed_at));
+    Ok(trashed)
+}
+
+#[tauri::command]
+pub fn restore_trashed_album(root_dir: String, id: String) -> Result<String, String> {
+    let root = canonical_root(&root_dir)?;
+    let entry = resolve_trash_entry(&root, &id)?;
+    let (_, name) = parse_trash_id(&id).ok_or("Invalid trash entry")?;
+    let origin = trash_origin_file(&trash_dir(&root), &id);
+    let parent = fs::read_to_string(&origin)
+        .ok()
+        .and_then(|relative| {
+            root.join(relative)
+                .parent()
+                .map(Path::to_path_buf)
+                .filter(|p| p.starts_with(&root) && p.is_dir())
+        })
+        .unwrap_or_else(|| root.clone());
+    let mut dest = parent.join(name);
+    let mut counter = 2;
+    while dest.exists() {
+        dest = parent.join(format!("{name} {counter}"));
+        counter += 1;
+    }
+    fs::rename(&entry, &dest).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(origin);
+    let _ = fs::remove_dir(trash_dir(&root));
+    enqueue_preload(&dest);
+    log::info!("restored {} → {}", entry.display(), dest.display());
+    Ok(dest.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+pub async fn empty_trash(root_dir: String, older_than_days: Option<u64>) -> Result<usize, String> {
+    let root = canonical_root(&root_dir)?;
+    let cutoff = older_than_days.map(|days| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+            .saturating_sub(days.saturating_mul(24 * 60 * 60 * 1000))
+    });
+    async_runtime::spawn_blocking(move || {
+        let trash = trash_dir(&root);
+        let Ok(entries) = fs::read_dir(&trash) else {
+            return Ok(0);
+        };
+        let mut removed = 0;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let id = entry.file_name().to_string_lossy().into_owned();
+            let Some((trashed_at, _)) = parse_trash_id(&id) else {
+                continue;
+            };
+            if cutoff.map(|c| trashed_at > c).unwrap_or(false) || !entry.path().is_dir() {
+                continue;
+            }
+            if let Err(e) = fs::remove_dir_all(entry.path()) {
+                log::warn!("empty_trash: failed to remove {}: {}", id, e);
+                continue;
+            }
+            let _ = fs::remove_file(trash_origin_file(&trash, &id));
+            removed += 1;
+        }
+        let _ = fs::remove_dir(&trash);
+        log::info!(
+            "emptied {} trashed albums under {}",
+            removed,
+            root.display()
+        );
+        Ok(removed)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn create_album(
+    root_dir: String,
+    name: String,
+    parent_id: Option<String>,
+) -> Result<DetachedAlbum, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Album name cannot be empty".to_string());
+    }
+    let safe_name = trimmed.replace(['/', '\\', ':'], "_");
+    if safe_name.starts_with(".room237-") || safe_name == "." || safe_name == ".." {
+        return Err(format!("\"{}\" is a reserved name", safe_name));
+    }
+
+    let root = canonical_root(&root_dir)?;
+    let parent = match parent_id.filter(|p| !p.trim().is_empty()) {
+        Some(p) => Some(resolve_album_dir(&root, &p)?),
+        None => None,
+    };
+    let path = parent.as_deref().unwrap_or(&root).join(&safe_name);
+    if path.exists() {
+        return Err(format!("Album \"{}\" already exists", safe_name));
+    }
+    fs::create_dir(&path).map_err(|e| e.to_string())?;
+    log::info!("created album {}", path.display());
+
+    let relative = |p: &Path| {
+        p.strip_prefix(&root)
+            .map_err(|e| e.to_string())
+            .map(normalized_relative_path)
+    };
+    Ok(DetachedAlbum {
+        path: path.to_string_lossy().into_owned(),
+        name: safe_name,
+        size: 0,
+        thumb_path: None,
+        relative_path: relative(&path)?,
+        parent: parent.as_deref().map(relative).transpose()?,
+        smart: None,
+        properties: AlbumProperties::default(),
+        offline: None,
+    })
+}
+
+#[tauri::command]
+pub fn delete_album(root_dir: String, album_id: String) -> Result<String, String> {
+    let root = canonical_root(&root_dir)?;
+    let dir = resolve_album_dir(&root, &album_id)?;
+    let trashed = move_to_trash(&root, &dir)?;
+    Ok(trashed.to_string_lossy().into_owned())
+}
+
+pub(crate) fn move_media_artifacts(
+    source_dir: &Path,
+    source_name: &str,
+    target_dir: &Path,
+    target_name: &str,
+) {
+    for (sub, ext) in [(".room237-thumb", "webp"), (".room237-metadata", "meta")] {
+        let from = source_dir.join(sub).join(format!("{source_name}.{ext}"));
+        if from.exists() {
+            let to_dir = target_dir.join(sub);
+            let _ = fs::create_dir_all(&to_dir);
+            let _ = fs::rename(&from, to_dir.join(format!("{target_name}.{ext}")));
+        }
+    }
+}
+
+fn merge_albums_blocking(
+    root: PathBuf,
+    source: PathBuf,
+    target: PathBuf,
+    policy: CollisionPolicy,
+) -> Result<MergeAlbumsResult, String> {
+    let mut skipped = Vec::new();
+    let mut failed = Vec::new();
+    let mut moves: Vec<(String, String)> = Vec::new();
+    {
+        let _guard = STORE_WRITE_LOCK.lock().unwrap();
+        for entry in fs::read_dir(&source).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if !path.is_file()
+                || !(has_extension(&path, IMAGE_EXTENSIONS)
+                    || has_extension(&path, VIDEO_EXTENSIONS))
+            {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let target_name = match place_in_dir(&path, &target, name, policy) {
+                Ok(Placement::Free(n) | Placement::Renamed(n) | Placement::Replace(n)) => n,
+                Ok(Placement::Skip) => {
+                    skipped.push(name.to_string());
+                    continue;
+                }
+                Ok(Placement::Conflict) => {
+                    failed.push(name.to_string());
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("merge_albums: failed to place {}: {}", path.display(), e);
+                    failed.push(name.to_string());
+                    continue;
+                }
+            };
+            if let Err(e) = fs::rename(&path, target.join(&target_name)) {
+                log::warn!("merge_albums: failed to move {}: {}", path.display(), e);
+                failed.push(name.to_string());
+                continue;
+            }
+            move_media_artifacts(&source, name, &target, &target_name);
+            moves.push((name.to_string(), target_name));
+        }
+        merge_album_meta_caller_holds_lock(&source, &target, &moves)?;
+    }
+
+    for child in fs::read_dir(&source).map_err(|e| e.to_string())? {
+        let path = child.map_err(|e| e.to_string())?.path();
+        if !is_album_dir(&path) {
+            continue;
+        }
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("album")
+            .to_string();
+        let mut dest = target.join(&name);
+        let mut counter = 2;
+        while dest.exists() {
+            dest = target.join(format!("{name} {counter}"));
+            counter += 1;
+        }
+        if let Err(e) = fs::rename(&path, &dest) {
+            log::warn!("merge_albums: failed to move {}: {}", path.display(), e);
+            failed.push(name);
+        }
+    }
+
+    let mut left_behind: Vec<String> = fs::read_dir(&source)
+        .map_err(|e| e.to_string())?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+        .filter(|n| !n.starts_with(".room237-"))
+        .collect();
+    left_behind.sort();
+
+    drop_preload_for_path(&source);
+    let trashed_path = if failed.is_empty() && left_behind.is_empty() {
+        Some(
+            move_to_trash(&root, &source)?
+                .to_string_lossy()
+                .into_owned(),
+        )
+    } else {
+        None
+    };
+    enqueue_preload(&target);
+    log::info!(
+        "merged {} → {}: {} moved, {} skipped, {} failed, {} left behind",
+        source.display(),
+        target.display(),
+        moves.len(),
+        skipped.len(),
+        failed.len(),
+        left_behind.len()
+    );
+    Ok(MergeAlbumsResult {
+        target_path: target.to_string_lossy().into_owned(),
+        moved: moves.len(),
+        skipped,
+        failed,
+        left_behind,
+        trashed_path,
+    })
+}
+
+#[tauri::command]
+pub async fn merge_albums(
+    root_dir: String,
+    source_id: String,
+    target_id: String,
+) -> Result<MergeAlbumsResult, String> {
+    let root = canonical_root(&root_dir)?;
+    let source = resolve_album_dir(&root, &source_id)?;
+    let target = resolve_album_dir(&root, &target_id)?;
+    if source == target {
+        return Err("Cannot merge an album into itself".to_string());
+    }
+    if target.starts_with(&source) {
+        return Err("Cannot merge an album into its own subtree".to_string());
+    }
+    let policy = read_settings().album.collision_policy;
+    async_runtime::spawn_blocking(move || merge_albums_blocking(root, source, target, policy))
+        .await
+        .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
@@ -1019,11 +1761,12 @@ pub async fn register_new_media(
     .map_err(|e| e.to_string())?
 }
 
-fn add_media_files_blocking(
+pub(crate) fn add_media_files_blocking(
     dir: String,
     files: Vec<IncomingFile>,
 ) -> Result<Vec<DetachedMediaEntry>, String> {
     let album_path = PathBuf::from(&dir);
+    ensure_online(&album_path)?;
     if !album_path.is_dir() {
         return Err(format!("{} is not a directory", album_path.display()));
     }
@@ -1079,7 +1822,7 @@ fn add_media_files_blocking(
     }
 
     let album_meta = read_album_meta(&album_path);
-    let settings = read_settings();
+    let settings = read_settings_for(&album_path);
 
     let added: Vec<DetachedMediaEntry> = copied_files
         .par_iter()
@@ -1115,12 +1858,19 @@ fn add_media_files_blocking(
                 .get(target_name.as_str())
                 .map(|e| if e.favorite { Some(true) } else { None })
                 .unwrap_or(None);
+            let tags = album_meta
+                .files
+                .get(target_name.as_str())
+                .map(|e| e.tags.clone())
+                .unwrap_or_default();
 
             log::info!("registered new media {}", path.display());
             Some(DetachedMediaEntry {
                 meta: meta_str,
                 name: path.file_name().unwrap().to_string_lossy().into_owned(),
                 favorite,
+                tags,
+                thumb: None,
             })
         })
         .collect();
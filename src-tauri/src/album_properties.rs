@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
+    library::ensure_online,
+    metadata::{update_album_meta, AlbumProperties, AlbumSortDir, AlbumSortKey},
+    util::has_extension,
+};
+
+const MAX_TITLE_LEN: usize = 200;
+const MAX_DESCRIPTION_LEN: usize = 10_000;
+
+fn update_properties<F>(album_path: &str, update: F) -> Result<AlbumProperties, String>
+where
+    F: FnOnce(&Path, &mut AlbumProperties) -> Result<(), String>,
+{
+    let dir = PathBuf::from(album_path);
+    ensure_online(&dir)?;
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", album_path));
+    }
+    update_album_meta(&dir, |album| update(&dir, &mut album.properties))
+        .map(|album| album.properties)
+}
+
+fn normalized_text(value: Option<String>, max_len: usize) -> Result<Option<String>, String> {
+    let Some(value) = value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    else {
+        return Ok(None);
+    };
+    if value.chars().count() > max_len {
+        return Err(format!("Text is longer than {} characters", max_len));
+    }
+    Ok(Some(value))
+}
+
+#[tauri::command]
+pub fn set_album_title(
+    album_path: String,
+    title: Option<String>,
+) -> Result<AlbumProperties, String> {
+    let title = normalized_text(title, MAX_TITLE_LEN)?;
+    update_properties(&album_path, |_, props| {
+        props.title = title;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn set_album_description(
+    album_path: String,
+    description: Option<String>,
+) -> Result<AlbumProperties, String> {
+    let description = normalized_text(description, MAX_DESCRIPTION_LEN)?;
+    update_properties(&album_path, |_, props| {
+        props.description = description;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn set_album_cover(
+    album_path: String,
+    media: Option<String>,
+) -> Result<AlbumProperties, String> {
+    update_properties(&album_path, |dir, props| {
+        if let Some(name) = media.as_deref() {
+            let path = dir.join(name);
+            let is_media =
+                has_extension(&path, IMAGE_EXTENSIONS) || has_extension(&path, VIDEO_EXTENSIONS);
+            if path.parent() != Some(dir) || !path.is_file() || !is_media {
+                return Err(format!("{} is not a media file in this album", name));
+            }
+        }
+        props.cover = media;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn set_album_sort(
+    album_path: String,
+    sort_key: Option<AlbumSortKey>,
+    sort_dir: Option<AlbumSortDir>,
+) -> Result<AlbumProperties, String> {
+    update_properties(&album_path, |_, props| {
+        props.sort_key = sort_key;
+        props.sort_dir = sort_dir;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn set_album_hidden(album_path: String, hidden: bool) -> Result<AlbumProperties, String> {
+    update_properties(&album_path, |_, props| {
+        props.hidden = hidden;
+        Ok(())
+    })
+}
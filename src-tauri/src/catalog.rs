@@ -0,0 +1,372 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant, UNIX_EPOCH},
+};
+
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{path::BaseDirectory, AppHandle, Manager, Wry};
+
+use crate::{
+    album::{walk_album_entries, AlbumDirEntry},
+    metadata::{
+        album_meta_stamp, read_album_meta, unpack_file_meta, AlbumProperties, DetachedAlbum,
+        DetachedMediaEntry, FileMetaEntry,
+    },
+    search::{IndexedMedia, LibrarySnapshot},
+    util::newer_than,
+};
+
+const CATALOG_DIR: &str = "catalogs";
+const CATALOG_FILE: &str = "catalog.json";
+const CATALOG_VERSION: u32 = 1;
+const REFRESH_DELAY: Duration = Duration::from_secs(5);
+const REFRESH_MIN_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const THUMBS_PER_ALBUM: usize = 24;
+
+static CATALOG_BASE: OnceCell<PathBuf> = OnceCell::new();
+static REFRESHING: Lazy<Mutex<HashMap<PathBuf, bool>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static LAST_REFRESH: Lazy<Mutex<HashMap<PathBuf, Instant>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CatalogFile {
+    version: u32,
+    #[serde(default)]
+    root: PathBuf,
+    #[serde(default)]
+    updated_at: i64,
+    #[serde(default)]
+    albums: Vec<CatalogAlbum>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CatalogAlbum {
+    relative_path: String,
+    name: String,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    stamp: u64,
+    #[serde(default)]
+    thumbs_stamp: u64,
+    #[serde(default)]
+    thumb: Option<String>,
+    #[serde(default)]
+    properties: AlbumProperties,
+    #[serde(default)]
+    files: BTreeMap<String, FileMetaEntry>,
+}
+
+pub fn init_catalogs(app: &AppHandle<Wry>) -> Result<(), String> {
+    let base = app
+        .path()
+        .resolve(CATALOG_DIR, BaseDirectory::AppData)
+        .map_err(|e| e.to_string())?;
+    let _ = CATALOG_BASE.set(base);
+    Ok(())
+}
+
+fn catalog_dir(root: &Path) -> Option<PathBuf> {
+    let mut hasher = Sha256::new();
+    hasher.update(root.to_string_lossy().as_bytes());
+    let key = hex::encode(hasher.finalize());
+    Some(CATALOG_BASE.get()?.join(&key[..16]))
+}
+
+fn thumbs_dir(root: &Path) -> Option<PathBuf> {
+    catalog_dir(root).map(|d| d.join("thumbs"))
+}
+
+fn read_catalog(root: &Path) -> Option<CatalogFile> {
+    let bytes = fs::read(catalog_dir(root)?.join(CATALOG_FILE)).ok()?;
+    serde_json::from_slice::<CatalogFile>(&bytes)
+        .ok()
+        .filter(|c| c.version == CATALOG_VERSION && c.root == root)
+}
+
+fn write_catalog(root: &Path, catalog: &CatalogFile) -> Result<(), String> {
+    let dir = catalog_dir(root).ok_or_else(|| "Catalog directory is not set".to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_vec(catalog).map_err(|e| e.to_string())?;
+    let tmp = dir.join(format!("{CATALOG_FILE}.tmp"));
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, dir.join(CATALOG_FILE)).map_err(|e| e.to_string())
+}
+
+pub fn has_catalog(root: &Path) -> bool {
+    catalog_dir(root)
+        .map(|d| d.join(CATALOG_FILE).is_file())
+        .unwrap_or(false)
+}
+
+pub fn forget_catalog(root: &Path) {
+    if let Some(dir) = catalog_dir(root) {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+fn dir_stamp(dir: &Path) -> u64 {
+    dir.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+fn sync_thumbs(source: &Path, target: &Path, wanted: &[String]) -> HashSet<String> {
+    let mut present = HashSet::new();
+    for name in wanted {
+        let path = source.join(name);
+        if !path.is_file() {
+            continue;
+        }
+        if present.is_empty() {
+            let _ = fs::create_dir_all(target);
+        }
+        let copy = target.join(name);
+        if newer_than(&copy, &path).unwrap_or(false) || fs::copy(&path, &copy).is_ok() {
+            present.insert(name.clone());
+        }
+    }
+    if let Ok(entries) = fs::read_dir(target) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !present.contains(&name) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    present
+}
+
+fn catalog_album(
+    entry: &AlbumDirEntry,
+    previous: Option<CatalogAlbum>,
+    thumbs: &Path,
+) -> CatalogAlbum {
+    let stamp = album_meta_stamp(&entry.path);
+    let thumb_source = entry.path.join(".room237-thumb");
+    let thumbs_stamp = dir_stamp(&thumb_source);
+    if let Some(prev) = previous
+        .as_ref()
+        .filter(|p| p.stamp == stamp && stamp != 0 && p.thumbs_stamp == thumbs_stamp)
+    {
+        return CatalogAlbum {
+            name: entry.name.clone(),
+            parent: entry.parent.clone(),
+            ..prev.clone()
+        };
+    }
+    let (properties, files) = match previous {
+        Some(prev) if prev.stamp == stamp && stamp != 0 => (prev.properties, prev.files),
+        _ => {
+            let meta = read_album_meta(&entry.path);
+            (meta.properties, meta.files.into_iter().collect())
+        }
+    };
+    let cover = properties
+        .cover
+        .as_ref()
+        .map(|cover| format!("{cover}.webp"));
+    let wanted: Vec<String> = cover
+        .iter()
+        .cloned()
+        .chain(
+            files
+                .keys()
+                .map(|name| format!("{name}.webp"))
+                .filter(|t| Some(t) != cover.as_ref())
+                .take(THUMBS_PER_ALBUM),
+        )
+        .collect();
+    let present = sync_thumbs(&thumb_source, &thumbs.join(&entry.relative_path), &wanted);
+    let thumb = wanted.into_iter().find(|t| present.contains(t));
+    CatalogAlbum {
+        relative_path: entry.relative_path.clone(),
+        name: entry.name.clone(),
+        parent: entry.parent.clone(),
+        stamp,
+        thumbs_stamp,
+        thumb,
+        properties,
+        files,
+    }
+}
+
+pub fn refresh_catalog(root: &Path) -> Result<usize, String> {
+    let thumbs = thumbs_dir(root).ok_or_else(|| "Catalog directory is not set".to_string())?;
+    let mut previous: HashMap<String, CatalogAlbum> = read_catalog(root)
+        .map(|c| {
+            c.albums
+                .into_iter()
+                .map(|a| (a.relative_path.clone(), a))
+                .collect()
+        })
+        .unwrap_or_default();
+    let entries = walk_album_entries(root)?;
+    let albums: Vec<CatalogAlbum> = entries
+        .iter()
+        .map(|entry| catalog_album(entry, previous.remove(&entry.relative_path), &thumbs))
+        .collect();
+    for relative in previous.keys() {
+        let _ = fs::remove_dir_all(thumbs.join(relative));
+    }
+    let count = albums.len();
+    write_catalog(
+        root,
+        &CatalogFile {
+            version: CATALOG_VERSION,
+            root: root.to_path_buf(),
+            updated_at: chrono::Utc::now().timestamp_millis(),
+            albums,
+        },
+    )?;
+    Ok(count)
+}
+
+/// Queues a catalog refresh for `root`, at most once per `REFRESH_MIN_INTERVAL`.
+pub fn schedule_catalog_refresh(root: &Path) {
+    if CATALOG_BASE.get().is_none() {
+        return;
+    }
+    let recent = LAST_REFRESH
+        .lock()
+        .unwrap()
+        .get(root)
+        .map(|t| t.elapsed() < REFRESH_MIN_INTERVAL)
+        .unwrap_or(false);
+    if recent && has_catalog(root) {
+        return;
+    }
+    {
+        let mut refreshing = REFRESHING.lock().unwrap();
+        if let Some(rerun) = refreshing.get_mut(root) {
+            *rerun = true;
+            return;
+        }
+        refreshing.insert(root.to_path_buf(), false);
+    }
+    let root = root.to_path_buf();
+    thread::spawn(move || loop {
+        thread::sleep(REFRESH_DELAY);
+        if root.is_dir() {
+            LAST_REFRESH
+                .lock()
+                .unwrap()
+                .insert(root.clone(), Instant::now());
+            match refresh_catalog(&root) {
+                Ok(count) => log::debug!("catalog refreshed {} ({count} albums)", root.display()),
+                Err(e) => log::warn!("catalog refresh failed for {}: {}", root.display(), e),
+            }
+        }
+        let mut refreshing = REFRESHING.lock().unwrap();
+        match refreshing.get_mut(&root) {
+            Some(rerun) if *rerun => *rerun = false,
+            _ => {
+                refreshing.remove(&root);
+                return;
+            }
+        }
+    });
+}
+
+pub(crate) fn catalog_albums(root: &Path) -> Option<Vec<DetachedAlbum>> {
+    let catalog = read_catalog(root)?;
+    let thumbs = thumbs_dir(root)?;
+    Some(
+        catalog
+            .albums
+            .into_iter()
+            .map(|album| DetachedAlbum {
+                path: root
+                    .join(&album.relative_path)
+                    .to_string_lossy()
+                    .into_owned(),
+                name: album.name,
+                size: album.files.len(),
+                thumb_path: album.thumb.map(|t| {
+                    thumbs
+                        .join(&album.relative_path)
+                        .join(t)
+                        .to_string_lossy()
+                        .into_owned()
+                }),
+                relative_path: album.relative_path,
+                parent: album.parent,
+                smart: None,
+                offline: Some(true),
+                properties: album.properties,
+            })
+            .collect(),
+    )
+}
+
+pub(crate) fn catalog_album_media(root: &Path, dir: &Path) -> Option<Vec<DetachedMediaEntry>> {
+    let relative = dir
+        .strip_prefix(root)
+        .ok()?
+        .to_string_lossy()
+        .replace('\\', "/");
+    let album = read_catalog(root)?
+        .albums
+        .into_iter()
+        .find(|a| a.relative_path == relative)?;
+    let thumbs = thumbs_dir(root)?.join(&relative);
+    Some(
+        album
+            .files
+            .into_iter()
+            .filter_map(|(name, entry)| {
+                let thumb = thumbs.join(format!("{name}.webp"));
+                Some(DetachedMediaEntry {
+                    meta: entry.meta?,
+                    thumb: thumb
+                        .is_file()
+                        .then(|| thumb.to_string_lossy().into_owned()),
+                    favorite: if entry.favorite { Some(true) } else { None },
+                    tags: entry.tags,
+                    name,
+                })
+            })
+            .collect(),
+    )
+}
+
+pub(crate) fn catalog_snapshot(root: &Path) -> Option<LibrarySnapshot> {
+    let catalog = read_catalog(root)?;
+    let mut albums = Vec::with_capacity(catalog.albums.len());
+    let mut media = Vec::new();
+    for (idx, album) in catalog.albums.into_iter().enumerate() {
+        for (name, entry) in album.files {
+            let Some(meta) = entry.meta.as_deref().and_then(|m| unpack_file_meta(m).ok()) else {
+                continue;
+            };
+            media.push(IndexedMedia {
+                album: idx,
+                name,
+                entry,
+                meta,
+            });
+        }
+        albums.push(AlbumDirEntry {
+            path: root.join(&album.relative_path),
+            name: album.name,
+            relative_path: album.relative_path,
+            parent: album.parent,
+        });
+    }
+    Some(LibrarySnapshot {
+        albums,
+        media,
+        thumbs: thumbs_dir(root),
+    })
+}
@@ -6,7 +6,9 @@ use tauri::{AppHandle, Wry};
 use crate::{
     album::walk_album_paths,
     constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
+    library::ensure_online,
     metadata::{get_file_metadata, read_album_meta, write_album_meta},
+    smart_albums::SMART_ALBUMS_FILE,
     thumb::ensure_thumb,
     util::{has_extension, heic_to_jpeg},
 };
@@ -14,6 +16,7 @@ use crate::{
 #[tauri::command]
 pub async fn rebuild_thumbnails(root_dir: String) -> Result<u64, String> {
     let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
     if !root.is_dir() {
         return Err(format!("{} is not a directory", root.display()));
     }
@@ -52,6 +55,7 @@ pub async fn rebuild_thumbnails(root_dir: String) -> Result<u64, String> {
 pub async fn rebuild_metadata(app: AppHandle<Wry>, root_dir: String) -> Result<u64, String> {
     let _ = &app;
     let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
     if !root.is_dir() {
         return Err(format!("{} is not a directory", root.display()));
     }
@@ -82,6 +86,7 @@ pub async fn rebuild_metadata(app: AppHandle<Wry>, root_dir: String) -> Result<u
 #[tauri::command]
 pub fn reset_duplicates(root_dir: String) -> Result<u64, String> {
     let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
     if !root.is_dir() {
         return Err(format!("{} is not a directory", root.display()));
     }
@@ -106,6 +111,7 @@ pub fn reset_duplicates(root_dir: String) -> Result<u64, String> {
 #[tauri::command]
 pub fn clear_room237_artifacts(root_dir: String) -> Result<u64, String> {
     let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
     if !root.is_dir() {
         return Err(format!("{} is not a directory", root.display()));
     }
@@ -141,12 +147,24 @@ pub fn clear_room237_artifacts(root_dir: String) -> Result<u64, String> {
         }
     }
 
-    for extra in [".room237-metadata", ".room237-thumb"] {
-        let path = root.join(extra);
-        if path.exists() {
-            let _ = fs::remove_dir_all(&path);
+    let root_meta = root.join(".room237-metadata");
+    if let Ok(entries) = fs::read_dir(&root_meta) {
+        for entry in entries.flatten() {
+            if entry.file_name() == SMART_ALBUMS_FILE {
+                continue;
+            }
+            let path = entry.path();
+            let _ = if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
         }
     }
+    let root_thumbs = root.join(".room237-thumb");
+    if root_thumbs.exists() {
+        let _ = fs::remove_dir_all(&root_thumbs);
+    }
 
     Ok(cleared)
 }
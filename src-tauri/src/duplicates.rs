@@ -11,11 +11,13 @@ use tokio::task;
 
 use crate::{
     constants::IMAGE_EXTENSIONS,
+    library::ensure_online,
     metadata::{
         mark_hash_failed, read_album_meta, write_album_file_hash, write_album_meta, AlbumMeta,
+        FileMetaEntry,
     },
     preload::{wait_for_album_hashes, TaskPriority},
-    settings::{read_settings, HashConfig},
+    settings::{read_settings_for, HashConfig},
     thumb::{ensure_thumb_with_settings, thumb_path},
     util::has_extension,
 };
@@ -76,7 +78,10 @@ fn decode_hash_bits_base64(s: &str, expected_bits: u32) -> Option<Vec<u64>> {
 }
 
 fn load_cached_bits_from_meta(meta: &AlbumMeta, name: &str, cfg: &HashConfig) -> Option<Vec<u64>> {
-    let entry = meta.files.get(name)?;
+    cached_hash_bits(meta.files.get(name)?, cfg)
+}
+
+pub(crate) fn cached_hash_bits(entry: &FileMetaEntry, cfg: &HashConfig) -> Option<Vec<u64>> {
     if entry.hash_version.as_deref() != Some(&cfg.hash_version) {
         return None;
     }
@@ -132,7 +137,7 @@ struct Entry {
 }
 
 #[inline(always)]
-fn within_threshold(a: &[u64], b: &[u64], threshold: u32) -> bool {
+pub(crate) fn within_threshold(a: &[u64], b: &[u64], threshold: u32) -> bool {
     let mut d = 0u32;
     for (ai, bi) in a.iter().zip(b.iter()) {
         d += (*ai ^ *bi).count_ones();
@@ -234,7 +239,7 @@ pub async fn find_duplicates(
     }
 
     let ignored = load_ignore_set(&dirp);
-    let settings = read_settings();
+    let settings = read_settings_for(&dirp);
     let hash_cfg = settings.hash_config();
     let max_files = settings.duplicates.max_files_per_album;
 
@@ -496,6 +501,7 @@ pub async fn find_duplicates(
 #[tauri::command]
 pub fn mark_non_duplicates(dir: String, files: Vec<String>) -> Result<(), String> {
     let dirp = PathBuf::from(&dir);
+    ensure_online(&dirp)?;
     if !dirp.is_dir() {
         return Err(format!("{} is not a directory", dirp.display()));
     }
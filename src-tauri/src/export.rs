@@ -0,0 +1,331 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ffmpeg_sidecar::command::FfmpegCommand;
+use serde::{Deserialize, Serialize};
+use tauri::{async_runtime, AppHandle, Emitter, Wry};
+
+use crate::{
+    album::next_available_name,
+    constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
+    jobs::{register_job, JobHandle},
+    metadata::{exif_camera_model, get_metadata_with_favorite, unpack_file_meta, DetachedFileMeta},
+    privacy::write_private_copy_cancellable,
+    settings::read_settings,
+    template::{render_file_name, validate_template, TemplateContext},
+    timeline::shot_date,
+    util::{apply_ffmpeg_tuning, has_extension, transcode_timeout, wait_with_cancel},
+};
+
+const DEFAULT_QUALITY: u8 = 85;
+const DEFAULT_FILENAME_TEMPLATE: &str = "{name}";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Original,
+    Jpeg,
+    Webp,
+    Avif,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPreset {
+    #[serde(default)]
+    pub max_dim: Option<u32>,
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub quality: Option<u8>,
+    #[serde(default)]
+    pub strip_metadata: bool,
+    #[serde(default)]
+    pub filename_template: Option<String>,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportState {
+    Running,
+    Finished,
+    Cancelled,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportProgress {
+    pub job_id: String,
+    pub state: ExportState,
+    pub total: usize,
+    pub done: usize,
+    pub failed: usize,
+    pub source: Option<String>,
+    pub output: Option<String>,
+    pub error: Option<String>,
+}
+
+fn quality_to_qscale(quality: u8) -> u32 {
+    31 - (quality.clamp(1, 100) as u32 - 1) * 29 / 99
+}
+
+fn quality_to_crf(quality: u8, max_crf: u32) -> u32 {
+    max_crf - (quality.clamp(1, 100) as u32 * max_crf) / 100
+}
+
+fn scale_filter(max_dim: u32, even: bool) -> String {
+    format!(
+        "scale=min(iw\\,{d}):min(ih\\,{d}):force_original_aspect_ratio=decrease{extra}",
+        d = max_dim,
+        extra = if even { ":force_divisible_by=2" } else { "" }
+    )
+}
+
+fn image_codec_args(ext: &str, quality: u8) -> Vec<String> {
+    match ext {
+        "jpg" | "jpeg" => vec![
+            "-c:v".into(),
+            "mjpeg".into(),
+            "-q:v".into(),
+            quality_to_qscale(quality).to_string(),
+        ],
+        "webp" => vec![
+            "-c:v".into(),
+            "libwebp".into(),
+            "-q:v".into(),
+            quality.to_string(),
+        ],
+        "avif" => vec![
+            "-c:v".into(),
+            "libaom-av1".into(),
+            "-crf".into(),
+            quality_to_crf(quality, 63).to_string(),
+            "-still-picture".into(),
+            "1".into(),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+fn run_ffmpeg(
+    input: &Path,
+    output: &Path,
+    args: Vec<String>,
+    is_video: bool,
+    job: &JobHandle,
+) -> Result<(), String> {
+    let mut cmd = FfmpegCommand::new();
+    apply_ffmpeg_tuning(&mut cmd, is_video);
+    let mut child = cmd
+        .input(input.to_string_lossy())
+        .arg("-y")
+        .args(args)
+        .output(output.to_string_lossy())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let status = wait_with_cancel(&mut child, transcode_timeout(input), &|| job.is_cancelled())
+        .inspect_err(|_| {
+            let _ = fs::remove_file(output);
+        })?;
+    if !status.success() {
+        let _ = fs::remove_file(output);
+        return Err(format!(
+            "ffmpeg failed {}→{}",
+            input.display(),
+            output.display()
+        ));
+    }
+    Ok(())
+}
+
+fn output_extension(input: &Path, preset: &ExportPreset, is_video: bool) -> String {
+    let original = input
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    if is_video {
+        return if preset.max_dim.is_some() {
+            "mp4".to_string()
+        } else {
+            original
+        };
+    }
+    match preset.format {
+        ExportFormat::Original => original,
+        ExportFormat::Jpeg => "jpg".to_string(),
+        ExportFormat::Webp => "webp".to_string(),
+        ExportFormat::Avif => "avif".to_string(),
+    }
+}
+
+fn export_one(
+    input: &Path,
+    target_dir: &Path,
+    preset: &ExportPreset,
+    index: usize,
+    job: &JobHandle,
+) -> Result<PathBuf, String> {
+    let is_image = has_extension(input, IMAGE_EXTENSIONS);
+    let is_video = has_extension(input, VIDEO_EXTENSIONS);
+    if !input.is_file() || !(is_image || is_video) {
+        return Err(format!("{} is not a media file", input.display()));
+    }
+    let meta: Option<DetachedFileMeta> = get_metadata_with_favorite(input)
+        .ok()
+        .and_then(|m| unpack_file_meta(&m.meta).ok());
+    let stem = input
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid filename")?;
+    let album = input
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+    let camera = exif_camera_model(input);
+    let ctx = TemplateContext {
+        name: stem,
+        album,
+        index,
+        taken: meta.and_then(|m| m.s.or(m.a)).and_then(shot_date),
+        width: meta.and_then(|m| m.w),
+        height: meta.and_then(|m| m.h),
+        camera: camera.as_deref(),
+    };
+    let ext = output_extension(input, preset, is_video);
+    let template = preset
+        .filename_template
+        .as_deref()
+        .unwrap_or(DEFAULT_FILENAME_TEMPLATE);
+    let file_name = render_file_name(template, &ctx, &ext)?;
+    let output = target_dir.join(next_available_name(target_dir, &file_name)?);
+    let quality = preset.quality.unwrap_or(DEFAULT_QUALITY).clamp(1, 100);
+
+    let transcode_image =
+        is_image && (preset.format != ExportFormat::Original || preset.max_dim.is_some());
+    if transcode_image {
+        let mut args = Vec::new();
+        if let Some(d) = preset.max_dim {
+            args.extend(["-vf".to_string(), scale_filter(d, false)]);
+        }
+        args.extend(image_codec_args(&ext, quality));
+        let metadata_source = if preset.strip_metadata { "-1" } else { "0" };
+        args.extend(["-map_metadata".to_string(), metadata_source.to_string()]);
+        run_ffmpeg(input, &output, args, false, job)?;
+    } else if is_video && preset.max_dim.is_some() {
+        let mut args = vec![
+            "-vf".to_string(),
+            scale_filter(preset.max_dim.unwrap_or_default(), true),
+            "-c:v".to_string(),
+            "libx264".to_string(),
+            "-crf".to_string(),
+            (18 + quality_to_crf(quality, 33)).to_string(),
+            "-c:a".to_string(),
+            "aac".to_string(),
+            "-b:a".to_string(),
+            "192k".to_string(),
+        ];
+        if preset.strip_metadata {
+            args.extend(["-map_metadata".to_string(), "-1".to_string()]);
+        }
+        run_ffmpeg(input, &output, args, true, job)?;
+    } else if preset.strip_metadata {
+        write_private_copy_cancellable(
+            input,
+            &output,
+            &read_settings().privacy.export_profile,
+            &|| job.is_cancelled(),
+        )?;
+    } else {
+        fs::copy(input, &output).map_err(|e| e.to_string())?;
+    }
+    Ok(output)
+}
+
+fn run_export(
+    app: AppHandle<Wry>,
+    job: JobHandle,
+    paths: Vec<PathBuf>,
+    target_dir: PathBuf,
+    preset: ExportPreset,
+) {
+    let mut progress = ExportProgress {
+        job_id: job.id.clone(),
+        state: ExportState::Running,
+        total: paths.len(),
+        done: 0,
+        failed: 0,
+        source: None,
+        output: None,
+        error: None,
+    };
+    for (i, input) in paths.iter().enumerate() {
+        if job.is_cancelled() {
+            progress.state = ExportState::Cancelled;
+            break;
+        }
+        progress.source = Some(input.to_string_lossy().into_owned());
+        let result = export_one(input, &target_dir, &preset, i + 1, &job);
+        if result.is_err() && job.is_cancelled() {
+            progress.state = ExportState::Cancelled;
+            break;
+        }
+        match result {
+            Ok(output) => {
+                progress.output = Some(output.to_string_lossy().into_owned());
+                progress.error = None;
+            }
+            Err(e) => {
+                log::warn!("export failed for {}: {}", input.display(), e);
+                progress.failed += 1;
+                progress.output = None;
+                progress.error = Some(e);
+            }
+        }
+        progress.done += 1;
+        let _ = app.emit("export-progress", progress.clone());
+    }
+    if !matches!(progress.state, ExportState::Cancelled) {
+        progress.state = ExportState::Finished;
+    }
+    progress.source = None;
+    progress.output = None;
+    progress.error = None;
+    log::info!(
+        "export {} → {}: {}/{} files, {} failed",
+        job.id,
+        target_dir.display(),
+        progress.done,
+        progress.total,
+        progress.failed
+    );
+    let _ = app.emit("export-progress", progress);
+}
+
+#[tauri::command]
+pub fn export_media(
+    app: AppHandle<Wry>,
+    paths: Vec<String>,
+    target_dir: String,
+    preset: ExportPreset,
+) -> Result<String, String> {
+    let target = PathBuf::from(&target_dir);
+    if !target.is_dir() {
+        return Err(format!("{} is not a directory", target_dir));
+    }
+    if paths.is_empty() {
+        return Err("Nothing to export".to_string());
+    }
+    if let Some(template) = preset.filename_template.as_deref() {
+        validate_template(template)?;
+    }
+    let job = register_job("export");
+    let job_id = job.id.clone();
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    async_runtime::spawn_blocking(move || run_export(app, job, paths, target, preset));
+    Ok(job_id)
+}
@@ -0,0 +1,336 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::async_runtime;
+
+use crate::{
+    album::{library_media_entry, walk_album_entries, LibraryMediaEntry},
+    index::{AlbumIndex, RootIndex},
+    metadata::{read_album_meta, FileMetaEntry},
+    thumb::thumb_path,
+};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+const DEFAULT_PLACE_RADIUS_KM: f64 = 25.0;
+const MAX_PLACE_RADIUS_KM: f64 = 1000.0;
+const MAX_CITY_DISTANCE_KM: f64 = 100.0;
+
+static GEO_INDEX: AlbumIndex<[f64; 2]> = AlbumIndex {
+    file: "geo.json",
+    lock: Mutex::new(()),
+    extract: indexed_gps,
+};
+
+static CITIES: Lazy<Vec<City>> = Lazy::new(|| {
+    include_str!("../resources/cities.csv")
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut cols = line.split(',');
+            Some(City {
+                name: cols.next()?.to_string(),
+                country: cols.next()?.to_string(),
+                lat: cols.next()?.parse().ok()?,
+                lon: cols.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+});
+
+struct City {
+    name: String,
+    country: String,
+    lat: f64,
+    lon: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoBounds {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoMediaEntry {
+    #[serde(flatten)]
+    pub media: LibraryMediaEntry,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Place {
+    pub name: Option<String>,
+    pub country: Option<String>,
+    pub lat: f64,
+    pub lon: f64,
+    pub count: usize,
+    pub thumb_path: Option<String>,
+    pub bounds: GeoBounds,
+}
+
+struct Cluster {
+    lat: f64,
+    lon: f64,
+    members: Vec<(String, String, [f64; 2])>,
+}
+
+fn indexed_gps(entry: &FileMetaEntry) -> Option<[f64; 2]> {
+    entry.gps
+}
+
+fn ensure_root(root_dir: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(root_dir);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    Ok(root)
+}
+
+fn haversine_km(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let (lat1, lat2) = (a[0].to_radians(), b[0].to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b[1] - a[1]).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
+
+fn wrap_lon(lon: f64) -> f64 {
+    (lon + 180.0).rem_euclid(360.0) - 180.0
+}
+
+fn in_bounds(point: [f64; 2], south: f64, west: f64, north: f64, east: f64) -> bool {
+    let lat_ok = point[0] >= south && point[0] <= north;
+    if east - west >= 360.0 {
+        return lat_ok;
+    }
+    let (west, east, lon) = (wrap_lon(west), wrap_lon(east), wrap_lon(point[1]));
+    let lon_ok = if west <= east {
+        lon >= west && lon <= east
+    } else {
+        lon >= west || lon <= east
+    };
+    lat_ok && lon_ok
+}
+
+fn nearest_city(point: [f64; 2]) -> Option<&'static City> {
+    CITIES
+        .iter()
+        .map(|c| (c, haversine_km(point, [c.lat, c.lon])))
+        .filter(|(_, d)| *d <= MAX_CITY_DISTANCE_KM)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(c, _)| c)
+}
+
+fn grid_cell(point: [f64; 2], cell_deg: f64, lon_cells: i64) -> (i64, i64) {
+    (
+        (point[0] / cell_deg).floor() as i64,
+        ((wrap_lon(point[1]) + 180.0) / cell_deg)
+            .floor()
+            .rem_euclid(lon_cells as f64) as i64,
+    )
+}
+
+fn cluster_points(index: &RootIndex<[f64; 2]>, radius_km: f64) -> Vec<Cluster> {
+    let mut points: Vec<(String, String, [f64; 2])> = index
+        .albums
+        .iter()
+        .flat_map(|(rel, album)| {
+            album
+                .files
+                .iter()
+                .map(move |(name, gps)| (rel.clone(), name.clone(), *gps))
+        })
+        .collect();
+    points.sort_by(|a, b| a.2[0].total_cmp(&b.2[0]).then(a.2[1].total_cmp(&b.2[1])));
+
+    let cell_deg = (radius_km / 111.0).max(1e-4);
+    let lon_cells = (360.0 / cell_deg).ceil() as i64;
+    let mut clusters: Vec<Cluster> = Vec::new();
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (rel, name, gps) in points {
+        let (cy, cx) = grid_cell(gps, cell_deg, lon_cells);
+        let lon_span = (1.0 / gps[0].to_radians().cos().max(0.01))
+            .ceil()
+            .min(lon_cells as f64) as i64;
+        let mut best: Option<(usize, f64)> = None;
+        let mut seen = HashSet::new();
+        for dy in -1..=1 {
+            for dx in -lon_span..=lon_span {
+                let cell = (cy + dy, (cx + dx).rem_euclid(lon_cells));
+                if !seen.insert(cell) {
+                    continue;
+                }
+                let Some(ids) = grid.get(&cell) else {
+                    continue;
+                };
+                for &id in ids {
+                    let d = haversine_km(gps, [clusters[id].lat, clusters[id].lon]);
+                    if d <= radius_km && best.map(|(_, bd)| d < bd).unwrap_or(true) {
+                        best = Some((id, d));
+                    }
+                }
+            }
+        }
+        match best {
+            Some((id, _)) => clusters[id].members.push((rel, name, gps)),
+            None => {
+                grid.entry((cy, cx)).or_default().push(clusters.len());
+                clusters.push(Cluster {
+                    lat: gps[0],
+                    lon: gps[1],
+                    members: vec![(rel, name, gps)],
+                });
+            }
+        }
+    }
+    clusters
+}
+
+fn place_thumbnail(root: &Path, cluster: &Cluster) -> Option<String> {
+    cluster.members.iter().find_map(|(rel, name, _)| {
+        let album_dir = root.join(rel);
+        thumb_path(&album_dir.join(name), &album_dir.join(".room237-thumb"))
+            .ok()
+            .filter(|t| t.exists())
+            .map(|t| t.to_string_lossy().into_owned())
+    })
+}
+
+fn build_place(root: &Path, cluster: &Cluster) -> Place {
+    let count = cluster.members.len();
+    // Offsets from the seed keep antimeridian clusters whole (west may exceed east).
+    let (mut lat_sum, mut dlon_sum) = (0.0, 0.0);
+    let (mut dlon_min, mut dlon_max) = (f64::MAX, f64::MIN);
+    let mut bounds = GeoBounds {
+        south: f64::MAX,
+        west: 0.0,
+        north: f64::MIN,
+        east: 0.0,
+    };
+    for (_, _, gps) in &cluster.members {
+        let dlon = wrap_lon(gps[1] - cluster.lon);
+        lat_sum += gps[0];
+        dlon_sum += dlon;
+        dlon_min = dlon_min.min(dlon);
+        dlon_max = dlon_max.max(dlon);
+        bounds.south = bounds.south.min(gps[0]);
+        bounds.north = bounds.north.max(gps[0]);
+    }
+    bounds.west = wrap_lon(cluster.lon + dlon_min);
+    bounds.east = wrap_lon(cluster.lon + dlon_max);
+    let center = [
+        lat_sum / count as f64,
+        wrap_lon(cluster.lon + dlon_sum / count as f64),
+    ];
+    let city = nearest_city(center);
+    Place {
+        name: city.map(|c| c.name.clone()),
+        country: city.map(|c| c.country.clone()),
+        lat: center[0],
+        lon: center[1],
+        count,
+        thumb_path: place_thumbnail(root, cluster),
+        bounds,
+    }
+}
+
+#[tauri::command]
+pub async fn list_media_in_bounds(
+    root_dir: String,
+    south: f64,
+    west: f64,
+    north: f64,
+    east: f64,
+) -> Result<Vec<GeoMediaEntry>, String> {
+    if south > north {
+        return Err("South edge must not exceed the north edge".to_string());
+    }
+    async_runtime::spawn_blocking(move || {
+        let root = ensure_root(&root_dir)?;
+        let index = GEO_INDEX.load_synced(&root)?;
+        let matched: HashMap<&str, HashSet<&str>> = index
+            .albums
+            .iter()
+            .filter_map(|(rel, album)| {
+                let names: HashSet<&str> = album
+                    .files
+                    .iter()
+                    .filter(|(_, gps)| in_bounds(**gps, south, west, north, east))
+                    .map(|(name, _)| name.as_str())
+                    .collect();
+                (!names.is_empty()).then_some((rel.as_str(), names))
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for album in walk_album_entries(&root)? {
+            let Some(names) = matched.get(album.relative_path.as_str()) else {
+                continue;
+            };
+            let album_meta = read_album_meta(&album.path);
+            for (name, entry) in album_meta.files.iter() {
+                let Some(gps) = entry.gps else {
+                    continue;
+                };
+                if !names.contains(name.as_str()) || !album.path.join(name).exists() {
+                    continue;
+                }
+                if let Some(media) = library_media_entry(&album, name, entry) {
+                    results.push(GeoMediaEntry {
+                        media,
+                        lat: gps[0],
+                        lon: gps[1],
+                    });
+                }
+            }
+        }
+        results.sort_by(|a, b| {
+            a.media
+                .album_name
+                .cmp(&b.media.album_name)
+                .then(a.media.name.cmp(&b.media.name))
+        });
+        Ok(results)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn get_places(root_dir: String, radius_km: Option<f64>) -> Result<Vec<Place>, String> {
+    let radius_km = radius_km
+        .filter(|r| r.is_finite())
+        .unwrap_or(DEFAULT_PLACE_RADIUS_KM)
+        .clamp(0.1, MAX_PLACE_RADIUS_KM);
+    async_runtime::spawn_blocking(move || {
+        let t_start = std::time::Instant::now();
+        let root = ensure_root(&root_dir)?;
+        let index = GEO_INDEX.load_synced(&root)?;
+        let mut places: Vec<Place> = cluster_points(&index, radius_km)
+            .iter()
+            .map(|cluster| build_place(&root, cluster))
+            .collect();
+        places.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+        log::info!(
+            "get_places {} radius={}km places={} elapsed={:?}",
+            root.display(),
+            radius_km,
+            places.len(),
+            t_start.elapsed()
+        );
+        Ok(places)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,663 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant, UNIX_EPOCH},
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{async_runtime, AppHandle, Emitter, Wry};
+
+use crate::{
+    album::{place_in_dir, walk_album_paths, Placement},
+    constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
+    jobs::{register_job, JobHandle},
+    library::ensure_online,
+    metadata::{datetime_original, exif_camera_model, probe, root_meta_file},
+    settings::{read_settings, CollisionPolicy},
+    template::{render_relative_dir, validate_template, TemplateContext},
+    timeline::shot_date,
+    util::{file_digest, has_extension},
+};
+
+const IMPORTS_DIR: &str = "imports";
+const DEFAULT_ALBUM_TEMPLATE: &str = "{year}/{year}-{month}-{day}";
+const UNDATED_ALBUM: &str = "Undated";
+const MANIFEST_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+static RUNNING_IMPORTS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    #[default]
+    Copy,
+    Move,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportItemStatus {
+    #[default]
+    Pending,
+    Imported,
+    Skipped,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportItem {
+    source: String,
+    size: u64,
+    #[serde(default)]
+    status: ImportItemStatus,
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    digest: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportManifest {
+    id: String,
+    source_dir: String,
+    album_template: String,
+    #[serde(default)]
+    mode: ImportMode,
+    #[serde(default)]
+    created: u64,
+    #[serde(default)]
+    finished: bool,
+    #[serde(default)]
+    items: Vec<ImportItem>,
+}
+
+impl ImportManifest {
+    fn count(&self, status: ImportItemStatus) -> usize {
+        self.items.iter().filter(|i| i.status == status).count()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub id: String,
+    pub source_dir: String,
+    pub album_template: String,
+    pub mode: ImportMode,
+    pub created: u64,
+    pub finished: bool,
+    pub total: usize,
+    pub imported: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub pending: usize,
+}
+
+impl From<&ImportManifest> for ImportSummary {
+    fn from(m: &ImportManifest) -> Self {
+        Self {
+            id: m.id.clone(),
+            source_dir: m.source_dir.clone(),
+            album_template: m.album_template.clone(),
+            mode: m.mode,
+            created: m.created,
+            finished: m.finished,
+            total: m.items.len(),
+            imported: m.count(ImportItemStatus::Imported),
+            skipped: m.count(ImportItemStatus::Skipped),
+            failed: m.count(ImportItemStatus::Failed),
+            pending: m.count(ImportItemStatus::Pending),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportStarted {
+    pub job_id: String,
+    pub import_id: String,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportState {
+    Scanning,
+    Running,
+    Finished,
+    Cancelled,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProgress {
+    pub job_id: String,
+    pub import_id: String,
+    pub state: ImportState,
+    pub total: usize,
+    pub done: usize,
+    pub imported: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub current: Option<String>,
+}
+
+struct LibraryDigests {
+    by_size: HashMap<u64, Vec<PathBuf>>,
+    digests: HashMap<PathBuf, String>,
+}
+
+impl LibraryDigests {
+    fn build(root: &Path) -> Result<Self, String> {
+        let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for album in walk_album_paths(root)? {
+            let files = fs::read_dir(&album).map_err(|e| e.to_string())?;
+            for file in files.filter_map(|e| e.ok().map(|e| e.path())) {
+                if !is_importable(&file) {
+                    continue;
+                }
+                if let Ok(meta) = file.metadata() {
+                    by_size.entry(meta.len()).or_default().push(file);
+                }
+            }
+        }
+        Ok(Self {
+            by_size,
+            digests: HashMap::new(),
+        })
+    }
+
+    fn digest_of(&mut self, path: &Path) -> Option<String> {
+        if let Some(d) = self.digests.get(path) {
+            return Some(d.clone());
+        }
+        let digest = file_digest(path).ok()?;
+        self.digests.insert(path.to_path_buf(), digest.clone());
+        Some(digest)
+    }
+
+    fn find(&mut self, size: u64, digest: &str) -> Option<PathBuf> {
+        let candidates = self.by_size.get(&size)?.clone();
+        candidates
+            .into_iter()
+            .find(|candidate| self.digest_of(candidate).as_deref() == Some(digest))
+    }
+
+    fn insert(&mut self, path: PathBuf, size: u64, digest: String) {
+        self.by_size.entry(size).or_default().push(path.clone());
+        self.digests.insert(path, digest);
+    }
+}
+
+fn imports_dir(root: &Path) -> PathBuf {
+    root_meta_file(root, IMPORTS_DIR)
+}
+
+fn manifest_path(root: &Path, id: &str) -> PathBuf {
+    imports_dir(root).join(format!("{id}.json"))
+}
+
+fn validate_import_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Invalid import id \"{}\"", id));
+    }
+    Ok(())
+}
+
+fn read_manifest(root: &Path, id: &str) -> Result<ImportManifest, String> {
+    validate_import_id(id)?;
+    let txt = fs::read_to_string(manifest_path(root, id))
+        .map_err(|_| format!("Import {} not found", id))?;
+    serde_json::from_str(&txt).map_err(|e| e.to_string())
+}
+
+fn write_manifest(root: &Path, manifest: &ImportManifest) -> Result<(), String> {
+    let path = manifest_path(root, &manifest.id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    let json = serde_json::to_string(manifest).map_err(|e| e.to_string())?;
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+fn ensure_root(root_dir: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(root_dir);
+    ensure_online(&root)?;
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    Ok(root)
+}
+
+fn is_importable(path: &Path) -> bool {
+    has_extension(path, IMAGE_EXTENSIONS) || has_extension(path, VIDEO_EXTENSIONS)
+}
+
+fn scan_source(
+    dir: &Path,
+    out: &mut Vec<ImportItem>,
+    visited: &mut HashSet<PathBuf>,
+) -> Result<(), String> {
+    let canonical = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    if !visited.insert(canonical) {
+        log::debug!("import: skipping {} (already scanned)", dir.display());
+        return Ok(());
+    }
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .collect();
+    entries.sort();
+    for path in entries {
+        let hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with('.'))
+            .unwrap_or(true);
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            scan_source(&path, out, visited)?;
+        } else if is_importable(&path) {
+            let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+            out.push(ImportItem {
+                source: path.to_string_lossy().into_owned(),
+                size,
+                status: ImportItemStatus::Pending,
+                target: None,
+                digest: None,
+                error: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn capture_time(path: &Path) -> Option<u64> {
+    let shot = if has_extension(path, VIDEO_EXTENSIONS) {
+        probe(&path.to_string_lossy(), true)
+            .ok()
+            .and_then(|p| p.shoot)
+    } else {
+        datetime_original(path)
+    };
+    shot.or_else(|| {
+        path.metadata()
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+    })
+}
+
+fn album_dir_for(root: &Path, template: &str, source: &Path) -> PathBuf {
+    let camera = exif_camera_model(source);
+    let ctx = TemplateContext {
+        name: source.file_stem().and_then(|s| s.to_str()).unwrap_or(""),
+        album: "",
+        index: 0,
+        taken: capture_time(source).and_then(shot_date),
+        width: None,
+        height: None,
+        camera: camera.as_deref(),
+    };
+    match render_relative_dir(template, &ctx) {
+        Ok(rel) => root.join(rel),
+        Err(e) => {
+            log::debug!("import: {} falls back to undated: {}", source.display(), e);
+            root.join(UNDATED_ALBUM)
+        }
+    }
+}
+
+fn staging_path(target: &Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{name}.room237-part"))
+}
+
+/// Copies (or moves) `source` onto `target` through a staging file next to it.
+fn place_file(
+    source: &Path,
+    target: &Path,
+    staged: &Path,
+    mode: ImportMode,
+    created: bool,
+) -> Result<(), String> {
+    if mode == ImportMode::Move && fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+    if let Err(e) = fs::copy(source, staged) {
+        let _ = fs::remove_file(staged);
+        return Err(e.to_string());
+    }
+    if let Err(e) = fs::rename(staged, target) {
+        let _ = fs::remove_file(staged);
+        return Err(e.to_string());
+    }
+    if mode == ImportMode::Move {
+        if let Err(e) = fs::remove_file(source) {
+            if created {
+                let _ = fs::remove_file(target);
+            }
+            return Err(format!("Failed to remove {}: {}", source.display(), e));
+        }
+    }
+    Ok(())
+}
+
+fn import_item(
+    root: &Path,
+    template: &str,
+    mode: ImportMode,
+    item: &mut ImportItem,
+    library: &mut LibraryDigests,
+    policy: CollisionPolicy,
+    checkpoint: &mut dyn FnMut(&ImportItem) -> Result<(), String>,
+) -> Result<(), String> {
+    let source = PathBuf::from(&item.source);
+    if !source.is_file() {
+        let landed = item
+            .target
+            .as_ref()
+            .map(|t| Path::new(t).is_file())
+            .unwrap_or(false);
+        if mode == ImportMode::Move && landed {
+            item.status = ImportItemStatus::Imported;
+            return Ok(());
+        }
+        let moved = item
+            .digest
+            .clone()
+            .and_then(|digest| library.find(item.size, &digest));
+        if let Some(existing) = moved.filter(|_| mode == ImportMode::Move) {
+            item.status = ImportItemStatus::Imported;
+            item.target = Some(existing.to_string_lossy().into_owned());
+            return Ok(());
+        }
+        return Err(format!("{} no longer exists", source.display()));
+    }
+    item.target = None;
+    let size = source.metadata().map_err(|e| e.to_string())?.len();
+    let digest = file_digest(&source)?;
+    item.digest = Some(digest.clone());
+    if let Some(existing) = library.find(size, &digest) {
+        item.status = ImportItemStatus::Skipped;
+        item.target = Some(existing.to_string_lossy().into_owned());
+        return Ok(());
+    }
+
+    let album_dir = album_dir_for(root, template, &source);
+    fs::create_dir_all(&album_dir).map_err(|e| e.to_string())?;
+    let name = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid filename")?;
+    let (target, created) = match place_in_dir(&source, &album_dir, name, policy)? {
+        Placement::Free(n) | Placement::Renamed(n) => (album_dir.join(n), true),
+        Placement::Replace(n) if mode == ImportMode::Move => (album_dir.join(n), false),
+        Placement::Replace(n) => {
+            item.status = ImportItemStatus::Skipped;
+            item.target = Some(album_dir.join(n).to_string_lossy().into_owned());
+            return Ok(());
+        }
+        Placement::Skip => {
+            item.status = ImportItemStatus::Skipped;
+            item.target = Some(album_dir.join(name).to_string_lossy().into_owned());
+            return Ok(());
+        }
+        Placement::Conflict => {
+            return Err(format!(
+                "\"{}\" already exists in {}",
+                name,
+                album_dir.display()
+            ))
+        }
+    };
+    item.target = Some(target.to_string_lossy().into_owned());
+    if mode == ImportMode::Move {
+        checkpoint(item)?;
+    }
+    place_file(&source, &target, &staging_path(&target), mode, created)?;
+    library.insert(target, size, digest);
+    item.status = ImportItemStatus::Imported;
+    Ok(())
+}
+
+fn emit_import_progress(
+    app: &AppHandle<Wry>,
+    job: &JobHandle,
+    manifest: &ImportManifest,
+    state: ImportState,
+    current: Option<String>,
+) {
+    let summary = ImportSummary::from(manifest);
+    let _ = app.emit(
+        "import-progress",
+        ImportProgress {
+            job_id: job.id.clone(),
+            import_id: manifest.id.clone(),
+            state,
+            total: summary.total,
+            done: summary.total - summary.pending,
+            imported: summary.imported,
+            skipped: summary.skipped,
+            failed: summary.failed,
+            current,
+        },
+    );
+}
+
+fn run_import(app: AppHandle<Wry>, job: JobHandle, root: PathBuf, mut manifest: ImportManifest) {
+    let t_start = Instant::now();
+    emit_import_progress(&app, &job, &manifest, ImportState::Scanning, None);
+    let mut library = match LibraryDigests::build(&root) {
+        Ok(l) => l,
+        Err(e) => {
+            log::error!("import {}: failed to index library: {}", manifest.id, e);
+            emit_import_progress(&app, &job, &manifest, ImportState::Cancelled, None);
+            RUNNING_IMPORTS.lock().unwrap().remove(&manifest.id);
+            return;
+        }
+    };
+
+    let policy = read_settings().album.collision_policy;
+    let template = manifest.album_template.clone();
+    let mode = manifest.mode;
+    let mut last_flush = Instant::now();
+    let mut cancelled = false;
+    for idx in 0..manifest.items.len() {
+        if manifest.items[idx].status != ImportItemStatus::Pending {
+            continue;
+        }
+        if job.is_cancelled() {
+            cancelled = true;
+            break;
+        }
+        let mut item = manifest.items[idx].clone();
+        let mut checkpoint = |item: &ImportItem| {
+            manifest.items[idx] = item.clone();
+            write_manifest(&root, &manifest)
+        };
+        if let Err(e) = import_item(
+            &root,
+            &template,
+            mode,
+            &mut item,
+            &mut library,
+            policy,
+            &mut checkpoint,
+        ) {
+            log::warn!("import {}: {} failed: {}", manifest.id, item.source, e);
+            item.status = ImportItemStatus::Failed;
+            item.error = Some(e);
+        }
+        let current = item.source.clone();
+        manifest.items[idx] = item;
+        if last_flush.elapsed() >= MANIFEST_FLUSH_INTERVAL {
+            if let Err(e) = write_manifest(&root, &manifest) {
+                log::warn!("import {}: failed to save manifest: {}", manifest.id, e);
+            }
+            last_flush = Instant::now();
+        }
+        emit_import_progress(&app, &job, &manifest, ImportState::Running, Some(current));
+    }
+
+    manifest.finished = !cancelled;
+    if let Err(e) = write_manifest(&root, &manifest) {
+        log::warn!("import {}: failed to save manifest: {}", manifest.id, e);
+    }
+    let state = if cancelled {
+        ImportState::Cancelled
+    } else {
+        ImportState::Finished
+    };
+    emit_import_progress(&app, &job, &manifest, state, None);
+    RUNNING_IMPORTS.lock().unwrap().remove(&manifest.id);
+    let summary = ImportSummary::from(&manifest);
+    log::info!(
+        "import {} {}: imported={} skipped={} failed={} pending={} elapsed={:?}",
+        manifest.id,
+        if cancelled { "cancelled" } else { "finished" },
+        summary.imported,
+        summary.skipped,
+        summary.failed,
+        summary.pending,
+        t_start.elapsed()
+    );
+}
+
+fn start_import_job(
+    app: AppHandle<Wry>,
+    root: PathBuf,
+    manifest: ImportManifest,
+) -> Result<ImportStarted, String> {
+    if !RUNNING_IMPORTS.lock().unwrap().insert(manifest.id.clone()) {
+        return Err(format!("Import {} is already running", manifest.id));
+    }
+    let job = register_job("import");
+    let started = ImportStarted {
+        job_id: job.id.clone(),
+        import_id: manifest.id.clone(),
+    };
+    async_runtime::spawn_blocking(move || run_import(app, job, root, manifest));
+    Ok(started)
+}
+
+#[tauri::command]
+pub async fn import_media(
+    app: AppHandle<Wry>,
+    root_dir: String,
+    source_dir: String,
+    album_template: Option<String>,
+    mode: Option<ImportMode>,
+) -> Result<ImportStarted, String> {
+    let root = ensure_root(&root_dir)?;
+    let source = PathBuf::from(&source_dir);
+    if !source.is_dir() {
+        return Err(format!("{} is not a directory", source_dir));
+    }
+    if source.starts_with(&root) || root.starts_with(&source) {
+        return Err("Source folder overlaps the library".to_string());
+    }
+    let album_template = album_template
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_ALBUM_TEMPLATE.to_string());
+    validate_template(&album_template)?;
+
+    let scan_root = source.clone();
+    let items = async_runtime::spawn_blocking(move || {
+        let mut items = Vec::new();
+        scan_source(&scan_root, &mut items, &mut HashSet::new()).map(|_| items)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let now = chrono::Utc::now();
+    let mut hasher = Sha256::new();
+    hasher.update(format!(
+        "{}|{}",
+        source_dir,
+        now.timestamp_nanos_opt().unwrap_or(0)
+    ));
+    let manifest = ImportManifest {
+        id: format!(
+            "{}-{}",
+            now.format("%Y%m%d-%H%M%S"),
+            &hex::encode(hasher.finalize())[0..6]
+        ),
+        source_dir,
+        album_template,
+        mode: mode.unwrap_or_default(),
+        created: now.timestamp().max(0) as u64,
+        finished: false,
+        items,
+    };
+    write_manifest(&root, &manifest)?;
+    log::info!(
+        "import {} created from {} with {} files",
+        manifest.id,
+        manifest.source_dir,
+        manifest.items.len()
+    );
+    start_import_job(app, root, manifest)
+}
+
+#[tauri::command]
+pub fn resume_import(
+    app: AppHandle<Wry>,
+    root_dir: String,
+    import_id: String,
+) -> Result<ImportStarted, String> {
+    let root = ensure_root(&root_dir)?;
+    let mut manifest = read_manifest(&root, &import_id)?;
+    for item in manifest.items.iter_mut() {
+        if item.status == ImportItemStatus::Failed {
+            item.status = ImportItemStatus::Pending;
+            item.error = None;
+        }
+    }
+    manifest.finished = false;
+    start_import_job(app, root, manifest)
+}
+
+#[tauri::command]
+pub fn list_imports(root_dir: String) -> Result<Vec<ImportSummary>, String> {
+    let root = ensure_root(&root_dir)?;
+    let dir = imports_dir(&root);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut summaries: Vec<ImportSummary> = fs::read_dir(&dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .filter_map(|txt| serde_json::from_str::<ImportManifest>(&txt).ok())
+        .map(|m| ImportSummary::from(&m))
+        .collect();
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.created));
+    Ok(summaries)
+}
+
+#[tauri::command]
+pub fn forget_import(root_dir: String, import_id: String) -> Result<(), String> {
+    let root = ensure_root(&root_dir)?;
+    validate_import_id(&import_id)?;
+    if RUNNING_IMPORTS.lock().unwrap().contains(&import_id) {
+        return Err(format!("Import {} is still running", import_id));
+    }
+    fs::remove_file(manifest_path(&root, &import_id)).map_err(|e| e.to_string())
+}
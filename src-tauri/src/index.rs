@@ -0,0 +1,135 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::Path,
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    album::{normalized_relative_path, walk_album_entries},
+    metadata::{album_meta_stamp, read_album_meta, root_meta_file, AlbumMeta, FileMetaEntry},
+};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct IndexedAlbum<T> {
+    #[serde(default)]
+    pub(crate) stamp: u64,
+    #[serde(default = "BTreeMap::new")]
+    pub(crate) files: BTreeMap<String, T>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RootIndex<T> {
+    #[serde(default = "BTreeMap::new")]
+    pub(crate) albums: BTreeMap<String, IndexedAlbum<T>>,
+}
+
+impl<T> Default for RootIndex<T> {
+    fn default() -> Self {
+        Self {
+            albums: BTreeMap::new(),
+        }
+    }
+}
+
+pub(crate) struct AlbumIndex<T> {
+    pub(crate) file: &'static str,
+    pub(crate) lock: Mutex<()>,
+    pub(crate) extract: fn(&FileMetaEntry) -> Option<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> AlbumIndex<T> {
+    fn read(&self, root: &Path) -> RootIndex<T> {
+        fs::read_to_string(root_meta_file(root, self.file))
+            .ok()
+            .and_then(|txt| serde_json::from_str::<RootIndex<T>>(&txt).ok())
+            .unwrap_or_default()
+    }
+
+    fn write(&self, root: &Path, index: &RootIndex<T>) -> Result<(), String> {
+        let path = root_meta_file(root, self.file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    fn album_from_meta(&self, album: &AlbumMeta, stamp: u64) -> IndexedAlbum<T> {
+        IndexedAlbum {
+            stamp,
+            files: album
+                .files
+                .iter()
+                .filter_map(|(name, e)| (self.extract)(e).map(|v| (name.clone(), v)))
+                .collect(),
+        }
+    }
+
+    fn sync(&self, root: &Path, index: &mut RootIndex<T>) -> Result<bool, String> {
+        let mut changed = false;
+        let mut seen: HashSet<String> = HashSet::new();
+        for album in walk_album_entries(root)? {
+            let stamp = album_meta_stamp(&album.path);
+            seen.insert(album.relative_path.clone());
+            let fresh = index
+                .albums
+                .get(&album.relative_path)
+                .map(|a| a.stamp == stamp && stamp != 0)
+                .unwrap_or(false);
+            if fresh {
+                continue;
+            }
+            let meta = read_album_meta(&album.path);
+            index.albums.insert(
+                album.relative_path.clone(),
+                self.album_from_meta(&meta, album_meta_stamp(&album.path)),
+            );
+            changed = true;
+        }
+        let before = index.albums.len();
+        index.albums.retain(|rel, _| seen.contains(rel));
+        Ok(changed || before != index.albums.len())
+    }
+
+    pub(crate) fn load_synced(&self, root: &Path) -> Result<RootIndex<T>, String> {
+        let _guard = self.lock.lock().unwrap();
+        let mut index = self.read(root);
+        if self.sync(root, &mut index)? {
+            if let Err(e) = self.write(root, &index) {
+                log::warn!(
+                    "failed to persist {} for {}: {}",
+                    self.file,
+                    root.display(),
+                    e
+                );
+            }
+        }
+        Ok(index)
+    }
+
+    pub(crate) fn record_album(
+        &self,
+        root: &Path,
+        album_dir: &Path,
+        album: &AlbumMeta,
+    ) -> Result<(), String> {
+        let relative = album_dir.strip_prefix(root).map_err(|_| {
+            format!(
+                "{} is not under root {}",
+                album_dir.display(),
+                root.display()
+            )
+        })?;
+        let relative_path = normalized_relative_path(relative);
+        let _guard = self.lock.lock().unwrap();
+        let mut index = self.read(root);
+        index.albums.insert(
+            relative_path,
+            self.album_from_meta(album, album_meta_stamp(album_dir)),
+        );
+        self.write(root, &index)
+    }
+}
@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use once_cell::sync::Lazy;
+
+static JOBS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_JOB: AtomicU64 = AtomicU64::new(1);
+
+pub(crate) struct JobHandle {
+    pub(crate) id: String,
+    cancel: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        JOBS.lock().unwrap().remove(&self.id);
+    }
+}
+
+pub(crate) fn register_job(kind: &str) -> JobHandle {
+    let id = format!("{kind}-{}", NEXT_JOB.fetch_add(1, Ordering::SeqCst));
+    let cancel = Arc::new(AtomicBool::new(false));
+    JOBS.lock().unwrap().insert(id.clone(), cancel.clone());
+    JobHandle { id, cancel }
+}
+
+#[tauri::command]
+pub fn cancel_job(job_id: String) -> Result<(), String> {
+    match JOBS.lock().unwrap().get(&job_id) {
+        Some(cancel) => {
+            cancel.store(true, Ordering::Relaxed);
+            log::info!("cancel requested for job {}", job_id);
+            Ok(())
+        }
+        None => Err(format!("Job {} is not running", job_id)),
+    }
+}
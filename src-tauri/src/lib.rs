@@ -1,29 +1,77 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod album;
+mod album_properties;
+mod catalog;
 mod constants;
 mod debugging;
 mod duplicates;
+mod export;
+mod geo;
+mod import;
+mod index;
+mod jobs;
+mod library;
+mod memories;
 mod metadata;
 mod preload;
+mod preload_journal;
+mod privacy;
+mod rename;
+mod scheduler;
+mod search;
 mod settings;
+mod smart_albums;
+mod tags;
+mod template;
 mod thumb;
+mod timeline;
+mod upload;
 mod util;
 
 use anyhow::anyhow;
 use tauri::Manager;
 
 pub use album::{
-    add_media_files, get_album_media, get_album_size, get_albums_detached, list_favorites,
+    add_media_files, copy_album, copy_media_batch, create_album, delete_album, empty_trash,
+    get_album_media, get_album_size, get_albums_detached, list_favorites, list_trash, merge_albums,
     move_album, move_media, move_media_batch, register_new_media, rename_album,
+    restore_trashed_album,
 };
+pub use album_properties::{
+    set_album_cover, set_album_description, set_album_hidden, set_album_sort, set_album_title,
+};
+pub use catalog::init_catalogs;
 pub use debugging::{
     clear_room237_artifacts, rebuild_metadata, rebuild_thumbnails, reset_duplicates,
 };
 pub use duplicates::{find_duplicates, mark_non_duplicates};
+pub use export::export_media;
+pub use geo::{get_places, list_media_in_bounds};
+pub use import::{forget_import, import_media, list_imports, resume_import};
+pub use jobs::cancel_job;
+pub use library::{
+    add_library, configure_library, forget_library, list_libraries, load_library_registry,
+};
+pub use memories::get_on_this_day;
 pub use metadata::{get_file_metadata, set_media_favorite, set_media_timestamp};
-pub use preload::{is_preloading, lock_until_preloaded, set_allow_open};
+pub use preload::{
+    cancel_preload, close_library, focus_library, get_preload_status, is_preloading,
+    list_open_libraries, lock_until_preloaded, open_library, pause_preload, prioritize_album,
+    request_thumbnails, resume_preload, set_allow_open,
+};
+pub use privacy::{export_private_copies, find_media_with_location};
+pub use rename::rename_media_batch;
+pub use scheduler::report_user_activity;
+pub use search::search_media;
 pub use settings::{get_settings, reset_settings, update_settings, SettingsState};
+pub use smart_albums::{
+    create_smart_album, delete_smart_album, get_smart_album_media, list_smart_albums,
+    update_smart_album,
+};
+pub use tags::{add_tags, list_media_by_tag, list_tags, remove_tags};
+pub use timeline::{get_timeline, get_timeline_range};
+pub use upload::{abort_upload, append_upload_chunk, begin_upload, finish_upload};
 pub use util::get_file_manager_name;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -59,6 +107,62 @@ pub fn run() {
             get_settings,
             update_settings,
             reset_settings,
+            add_tags,
+            remove_tags,
+            list_tags,
+            list_media_by_tag,
+            search_media,
+            list_smart_albums,
+            create_smart_album,
+            update_smart_album,
+            delete_smart_album,
+            get_smart_album_media,
+            get_timeline,
+            get_timeline_range,
+            get_on_this_day,
+            list_media_in_bounds,
+            get_places,
+            export_private_copies,
+            find_media_with_location,
+            export_media,
+            cancel_job,
+            import_media,
+            resume_import,
+            list_imports,
+            forget_import,
+            begin_upload,
+            append_upload_chunk,
+            finish_upload,
+            abort_upload,
+            copy_media_batch,
+            copy_album,
+            create_album,
+            delete_album,
+            merge_albums,
+            rename_media_batch,
+            set_album_title,
+            set_album_description,
+            set_album_cover,
+            set_album_sort,
+            set_album_hidden,
+            pause_preload,
+            resume_preload,
+            cancel_preload,
+            prioritize_album,
+            get_preload_status,
+            request_thumbnails,
+            report_user_activity,
+            open_library,
+            close_library,
+            focus_library,
+            list_open_libraries,
+            list_libraries,
+            add_library,
+            forget_library,
+            configure_library,
+            list_trash,
+            restore_trashed_album,
+            empty_trash,
         ])
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -75,6 +179,10 @@ pub fn run() {
             let settings_state = SettingsState::load(&app.handle())
                 .map_err(|e| anyhow!("Failed to load settings: {e}"))?;
             app.manage(settings_state);
+            load_library_registry(&app.handle())
+                .map_err(|e| anyhow!("Failed to load libraries: {e}"))?;
+            init_catalogs(&app.handle())
+                .map_err(|e| anyhow!("Failed to resolve catalog directory: {e}"))?;
             ffmpeg_sidecar::download::auto_download().unwrap();
             Ok(())
         })
@@ -0,0 +1,268 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use tauri::{path::BaseDirectory, AppHandle, Manager, Wry};
+
+use crate::{
+    catalog::{forget_catalog, has_catalog},
+    preload::{apply_root_settings_change, close_root, is_root_open},
+    settings::{read_settings_for, SettingsOverrides},
+};
+
+const LIBRARIES_FILE: &str = "libraries.json";
+const TOUCH_INTERVAL_MS: i64 = 60 * 60 * 1000;
+
+static REGISTRY: OnceCell<LibraryRegistry> = OnceCell::new();
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryEntry {
+    pub root: PathBuf,
+    pub name: String,
+    #[serde(default)]
+    pub last_opened: Option<i64>,
+    #[serde(default)]
+    pub overrides: SettingsOverrides,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryInfo {
+    pub root: String,
+    pub name: String,
+    pub last_opened: Option<i64>,
+    pub available: bool,
+    pub cached: bool,
+    pub open: bool,
+    pub overrides: SettingsOverrides,
+}
+
+struct LibraryRegistry {
+    path: PathBuf,
+    entries: RwLock<Vec<LibraryEntry>>,
+}
+
+impl LibraryRegistry {
+    fn persist(&self, entries: &[LibraryEntry]) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+        fs::write(&self.path, json).map_err(|e| e.to_string())
+    }
+
+    fn modify<T>(&self, f: impl FnOnce(&mut Vec<LibraryEntry>) -> T) -> Result<T, String> {
+        let mut entries = self.entries.write().map_err(|e| e.to_string())?;
+        let result = f(&mut entries);
+        self.persist(&entries)?;
+        Ok(result)
+    }
+
+    /// Like `modify`, but only writes the registry when `f` reports a change.
+    fn modify_if(&self, f: impl FnOnce(&mut Vec<LibraryEntry>) -> bool) -> Result<bool, String> {
+        let mut entries = self.entries.write().map_err(|e| e.to_string())?;
+        let changed = f(&mut entries);
+        if changed {
+            self.persist(&entries)?;
+        }
+        Ok(changed)
+    }
+}
+
+pub fn load_library_registry(app: &AppHandle<Wry>) -> Result<(), String> {
+    let path = app
+        .path()
+        .resolve(LIBRARIES_FILE, BaseDirectory::AppConfig)
+        .map_err(|e| e.to_string())?;
+    let entries: Vec<LibraryEntry> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|txt| serde_json::from_str(&txt).ok())
+        .unwrap_or_default();
+    let _ = REGISTRY.set(LibraryRegistry {
+        path,
+        entries: RwLock::new(entries),
+    });
+    Ok(())
+}
+
+fn registry() -> Result<&'static LibraryRegistry, String> {
+    REGISTRY
+        .get()
+        .ok_or_else(|| "Library registry is not loaded".to_string())
+}
+
+fn default_name(root: &Path) -> String {
+    root.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.to_string_lossy().into_owned())
+}
+
+fn sort_recent(entries: &mut [LibraryEntry]) {
+    entries.sort_by_key(|e| std::cmp::Reverse(e.last_opened));
+}
+
+fn with_entry_for<T>(path: &Path, f: impl FnOnce(&LibraryEntry) -> T) -> Option<T> {
+    let entries = REGISTRY.get()?.entries.read().ok()?;
+    entries
+        .iter()
+        .filter(|e| path.starts_with(&e.root))
+        .max_by_key(|e| e.root.components().count())
+        .map(f)
+}
+
+pub fn library_overrides(path: &Path) -> Option<SettingsOverrides> {
+    with_entry_for(path, |e| e.overrides.clone())
+}
+
+pub fn known_library_root(path: &Path) -> Option<PathBuf> {
+    with_entry_for(path, |e| e.root.clone())
+}
+
+pub fn ensure_online(path: &Path) -> Result<(), String> {
+    let offline = with_entry_for(path, |e| (!e.root.is_dir()).then(|| e.name.clone())).flatten();
+    match offline {
+        Some(name) => Err(format!(
+            "Library \"{name}\" is offline. Reconnect its drive to make changes."
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn touch_library(root: &Path) {
+    let Ok(registry) = registry() else {
+        return;
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    let result = registry.modify_if(|entries| {
+        match entries.iter().position(|e| e.root == root) {
+            Some(idx) => {
+                // Skip the write when this library is already the most recent
+                // one and was touched a moment ago (e.g. an album list refresh).
+                let recent = entries[idx]
+                    .last_opened
+                    .map(|t| now - t < TOUCH_INTERVAL_MS)
+                    .unwrap_or(false);
+                if recent && idx == 0 {
+                    return false;
+                }
+                entries[idx].last_opened = Some(now);
+            }
+            None => entries.push(LibraryEntry {
+                root: root.to_path_buf(),
+                name: default_name(root),
+                last_opened: Some(now),
+                overrides: SettingsOverrides::default(),
+            }),
+        }
+        sort_recent(entries);
+        true
+    });
+    if let Err(e) = result {
+        log::warn!("failed to record library {}: {}", root.display(), e);
+    }
+}
+
+fn library_info(entry: &LibraryEntry) -> LibraryInfo {
+    LibraryInfo {
+        root: entry.root.to_string_lossy().into_owned(),
+        name: entry.name.clone(),
+        last_opened: entry.last_opened,
+        available: entry.root.is_dir(),
+        cached: has_catalog(&entry.root),
+        open: is_root_open(&entry.root),
+        overrides: entry.overrides.clone(),
+    }
+}
+
+#[tauri::command]
+pub fn list_libraries() -> Result<Vec<LibraryInfo>, String> {
+    let entries = registry()?.entries.read().map_err(|e| e.to_string())?;
+    Ok(entries.iter().map(library_info).collect())
+}
+
+#[tauri::command]
+pub fn add_library(root_dir: String, name: Option<String>) -> Result<LibraryInfo, String> {
+    let root = PathBuf::from(&root_dir);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let entry = registry()?.modify(|entries| {
+        let entry = match entries.iter_mut().find(|e| e.root == root) {
+            Some(entry) => {
+                if let Some(name) = name {
+                    entry.name = name;
+                }
+                entry.clone()
+            }
+            None => {
+                let entry = LibraryEntry {
+                    name: name.unwrap_or_else(|| default_name(&root)),
+                    root: root.clone(),
+                    last_opened: None,
+                    overrides: SettingsOverrides::default(),
+                };
+                entries.push(entry.clone());
+                entry
+            }
+        };
+        sort_recent(entries);
+        entry
+    })?;
+    Ok(library_info(&entry))
+}
+
+#[tauri::command]
+pub fn forget_library(root_dir: String) -> Result<(), String> {
+    let root = PathBuf::from(&root_dir);
+    let removed = registry()?.modify(|entries| {
+        let before = entries.len();
+        entries.retain(|e| e.root != root);
+        before != entries.len()
+    })?;
+    if !removed {
+        return Err(format!("{} is not a known library", root.display()));
+    }
+    if is_root_open(&root) {
+        close_root(&root);
+    }
+    forget_catalog(&root);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn configure_library(
+    root_dir: String,
+    overrides: SettingsOverrides,
+) -> Result<LibraryInfo, String> {
+    let root = PathBuf::from(&root_dir);
+    let overrides = overrides.clamp();
+    let previous = read_settings_for(&root);
+    let entry = registry()?.modify(|entries| {
+        let entry = match entries.iter_mut().find(|e| e.root == root) {
+            Some(entry) => {
+                entry.overrides = overrides.clone();
+                entry.clone()
+            }
+            None => {
+                let entry = LibraryEntry {
+                    name: default_name(&root),
+                    root: root.clone(),
+                    last_opened: None,
+                    overrides: overrides.clone(),
+                };
+                entries.push(entry.clone());
+                entry
+            }
+        };
+        sort_recent(entries);
+        entry
+    })?;
+    apply_root_settings_change(&root, &previous, &read_settings_for(&root));
+    Ok(library_info(&entry))
+}
@@ -0,0 +1,143 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::Serialize;
+use tauri::async_runtime;
+
+use crate::{
+    album::LibraryMediaEntry,
+    duplicates::{cached_hash_bits, within_threshold},
+    search::{scan_library, IndexedMedia},
+    settings::{read_settings_for, HashConfig},
+    timeline::shot_date,
+};
+
+const DEFAULT_PER_YEAR: usize = 12;
+const MAX_PER_YEAR: usize = 100;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryYear {
+    pub year: i32,
+    pub years_ago: i32,
+    pub total: usize,
+    pub items: Vec<LibraryMediaEntry>,
+}
+
+fn matches_day(item: &IndexedMedia, month: u32, day: u32, include_leap_day: bool) -> Option<i32> {
+    let date = shot_date(item.meta.s?)?;
+    let same_day = date.month() == month && date.day() == day;
+    let leap_day = include_leap_day && date.month() == 2 && date.day() == 29;
+    if same_day || leap_day {
+        Some(date.year())
+    } else {
+        None
+    }
+}
+
+fn pick_dissimilar<'a>(
+    mut candidates: Vec<&'a IndexedMedia>,
+    cfg: &HashConfig,
+    limit: usize,
+) -> Vec<&'a IndexedMedia> {
+    candidates.sort_by(|a, b| {
+        b.entry
+            .favorite
+            .cmp(&a.entry.favorite)
+            .then(a.meta.s.cmp(&b.meta.s))
+            .then(a.name.cmp(&b.name))
+    });
+
+    let mut picked: Vec<&IndexedMedia> = Vec::new();
+    let mut picked_hashes: Vec<Vec<u64>> = Vec::new();
+    let mut skipped: Vec<&IndexedMedia> = Vec::new();
+    for item in candidates {
+        if picked.len() >= limit {
+            break;
+        }
+        match cached_hash_bits(&item.entry, cfg) {
+            Some(bits) => {
+                if picked_hashes
+                    .iter()
+                    .any(|other| within_threshold(&bits, other, cfg.effective_threshold))
+                {
+                    skipped.push(item);
+                    continue;
+                }
+                picked_hashes.push(bits);
+                picked.push(item);
+            }
+            None => picked.push(item),
+        }
+    }
+
+    if picked.len() < limit {
+        let missing = limit - picked.len();
+        picked.extend(skipped.into_iter().take(missing));
+    }
+    picked.sort_by(|a, b| a.meta.s.cmp(&b.meta.s).then(a.name.cmp(&b.name)));
+    picked
+}
+
+#[tauri::command]
+pub async fn get_on_this_day(
+    root_dir: String,
+    date: Option<String>,
+    limit_per_year: Option<usize>,
+) -> Result<Vec<MemoryYear>, String> {
+    async_runtime::spawn_blocking(move || {
+        let root = PathBuf::from(&root_dir);
+        if !root.is_dir() {
+            return Err(format!("{} is not a directory", root.display()));
+        }
+        let today = match date.as_deref() {
+            Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date \"{d}\": {e}"))?,
+            None => Local::now().date_naive(),
+        };
+        let limit = limit_per_year
+            .unwrap_or(DEFAULT_PER_YEAR)
+            .clamp(1, MAX_PER_YEAR);
+        let include_leap_day =
+            today.month() == 2 && today.day() == 28 && today.with_day(29).is_none();
+        let hash_cfg = read_settings_for(&root).hash_config();
+
+        let snapshot = scan_library(&root)?;
+        let mut by_year: BTreeMap<i32, Vec<&IndexedMedia>> = BTreeMap::new();
+        for item in &snapshot.media {
+            if let Some(year) = matches_day(item, today.month(), today.day(), include_leap_day) {
+                if year < today.year() {
+                    by_year.entry(year).or_default().push(item);
+                }
+            }
+        }
+
+        let memories: Vec<MemoryYear> = by_year
+            .into_iter()
+            .rev()
+            .map(|(year, candidates)| {
+                let total = candidates.len();
+                let items = pick_dissimilar(candidates, &hash_cfg, limit)
+                    .into_iter()
+                    .map(|item| snapshot.to_entry(item))
+                    .collect();
+                MemoryYear {
+                    year,
+                    years_ago: today.year() - year,
+                    total,
+                    items,
+                }
+            })
+            .collect();
+
+        log::info!(
+            "get_on_this_day {} {} years={}",
+            root.display(),
+            today,
+            memories.len()
+        );
+        Ok(memories)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -1,26 +1,38 @@
 #[cfg(target_os = "windows")]
 use std::ffi::OsString;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     fs::{self, File},
     io::BufReader,
     path::{Path, PathBuf},
     process::Command,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
 };
 
 use crate::{
+    library::ensure_online,
     settings::read_settings,
-    util::{metadata_probe_timeout, run_command_with_timeout, STORE_WRITE_LOCK},
+    util::{
+        metadata_probe_timeout, run_command_with_timeout, set_low_priority_current_thread,
+        STORE_WRITE_LOCK,
+    },
 };
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use exif::{Field, In, Reader, Tag, Value};
+use once_cell::sync::Lazy;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 const META_DIR: &str = ".room237-metadata";
 const META_FILE_EXT: &str = ".meta";
 const ALBUM_META_FILE: &str = "album.json";
 
+#[derive(Clone, Copy)]
 pub struct DetachedFileMeta {
     pub a: Option<u64>,
     pub s: Option<u64>,
@@ -63,7 +75,7 @@ impl DetachedFileMeta {
     }
 }
 
-fn unpack_file_meta(packed: &str) -> Result<DetachedFileMeta, String> {
+pub(crate) fn unpack_file_meta(packed: &str) -> Result<DetachedFileMeta, String> {
     let p = packed
         .parse::<u128>()
         .map_err(|e| format!("Invalid packed meta: {e}"))?;
@@ -95,6 +107,10 @@ pub struct DetachedMediaEntry {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub favorite: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumb: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -106,6 +122,12 @@ pub struct DetachedAlbum {
     pub relative_path: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline: Option<bool>,
+    #[serde(flatten)]
+    pub properties: AlbumProperties,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -145,6 +167,44 @@ pub struct FileMetaEntry {
     pub thumb_failed: bool,
     #[serde(default)]
     pub meta_failed: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub gps: Option<[f64; 2]>,
+    #[serde(default)]
+    pub gps_scanned: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AlbumSortKey {
+    Shoot,
+    Added,
+    Name,
+    Random,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AlbumSortDir {
+    Asc,
+    Desc,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AlbumProperties {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_key: Option<AlbumSortKey>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_dir: Option<AlbumSortDir>,
+    #[serde(default)]
+    pub hidden: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -153,6 +213,8 @@ pub struct AlbumMeta {
     pub files: HashMap<String, FileMetaEntry>,
     #[serde(default)]
     pub duplicates_ignore: Vec<[String; 2]>,
+    #[serde(default)]
+    pub properties: AlbumProperties,
 }
 
 #[cfg(target_family = "unix")]
@@ -206,6 +268,14 @@ fn write_embedded_timestamp(_path: &Path, _timestamp: u64) -> Result<(), String>
     Err("Embedded timestamp storage unsupported on this platform".to_string())
 }
 
+pub(crate) fn copy_embedded_timestamp(src: &Path, dst: &Path) {
+    if let Some(ts) = read_embedded_timestamp(src) {
+        if let Err(e) = write_embedded_timestamp(dst, ts) {
+            log::warn!("Failed to copy timestamp to {}: {}", dst.display(), e);
+        }
+    }
+}
+
 fn meta_dir(dir: &Path) -> PathBuf {
     dir.join(META_DIR)
 }
@@ -218,6 +288,33 @@ fn ensure_meta_dir(dir: &Path) -> std::io::Result<()> {
     fs::create_dir_all(meta_dir(dir))
 }
 
+pub(crate) fn root_meta_file(root: &Path, name: &str) -> PathBuf {
+    meta_dir(root).join(name)
+}
+
+pub(crate) fn album_meta_stamp(dir: &Path) -> u64 {
+    let Ok(meta) = fs::metadata(album_meta_path(dir)) else {
+        return 0;
+    };
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let mut hasher = Sha256::new();
+    hasher.update(modified.to_le_bytes());
+    hasher.update(meta.len().to_le_bytes());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        hasher.update(meta.ino().to_le_bytes());
+        hasher.update(meta.ctime_nsec().to_le_bytes());
+    }
+    let digest = hasher.finalize();
+    u64::from_le_bytes(digest[..8].try_into().unwrap()).max(1)
+}
+
 pub(crate) fn write_file_meta(dir: &Path, name: &str, entry: &FileMetaEntry) -> Result<(), String> {
     let _guard = STORE_WRITE_LOCK.lock().unwrap();
     let mut album = read_album_meta_without_lock(dir);
@@ -388,6 +485,42 @@ pub(crate) fn write_album_meta(dir: &Path, data: &AlbumMeta) -> Result<(), Strin
     write_album_meta_without_lock(dir, data)
 }
 
+pub(crate) fn update_album_meta<F>(dir: &Path, update: F) -> Result<AlbumMeta, String>
+where
+    F: FnOnce(&mut AlbumMeta) -> Result<(), String>,
+{
+    update_album_meta_and(dir, update, |_| {})
+}
+
+pub(crate) fn update_album_meta_and<F, G>(
+    dir: &Path,
+    update: F,
+    written: G,
+) -> Result<AlbumMeta, String>
+where
+    F: FnOnce(&mut AlbumMeta) -> Result<(), String>,
+    G: FnOnce(&AlbumMeta),
+{
+    let _guard = STORE_WRITE_LOCK.lock().unwrap();
+    let mut album = read_album_meta_without_lock(dir);
+    update(&mut album)?;
+    write_album_meta_without_lock(dir, &album)?;
+    written(&album);
+    Ok(album)
+}
+
+fn insert_merged_entry(album: &mut AlbumMeta, name: &str, mut entry: FileMetaEntry) {
+    if let Some(existing) = album.files.get(name) {
+        entry.favorite |= existing.favorite;
+        for tag in &existing.tags {
+            if !entry.tags.contains(tag) {
+                entry.tags.push(tag.clone());
+            }
+        }
+    }
+    album.files.insert(name.to_string(), entry);
+}
+
 pub(crate) fn transfer_media_metadata_entry_caller_holds_lock(
     source_dir: &Path,
     source_name: &str,
@@ -399,7 +532,7 @@ pub(crate) fn transfer_media_metadata_entry_caller_holds_lock(
     write_album_meta_without_lock(source_dir, &source_album)?;
 
     let mut target_album = read_album_meta_without_lock(target_dir);
-    target_album.files.insert(target_name.to_string(), entry);
+    insert_merged_entry(&mut target_album, target_name, entry);
     write_album_meta_without_lock(target_dir, &target_album)?;
 
     Ok(())
@@ -417,13 +550,116 @@ pub(crate) fn transfer_media_metadata_batch_caller_holds_lock(
     let mut target_album = read_album_meta_without_lock(target_dir);
     for (source_name, target_name) in moves {
         let entry = source_album.files.remove(source_name).unwrap_or_default();
-        target_album.files.insert(target_name.clone(), entry);
+        insert_merged_entry(&mut target_album, target_name, entry);
+    }
+    write_album_meta_without_lock(source_dir, &source_album)?;
+    write_album_meta_without_lock(target_dir, &target_album)?;
+    Ok(())
+}
+
+pub(crate) fn rename_media_entries_caller_holds_lock(
+    dir: &Path,
+    renames: &[(String, String)],
+) -> Result<(), String> {
+    if renames.is_empty() {
+        return Ok(());
+    }
+    let mut album = read_album_meta_without_lock(dir);
+    let moved: Vec<(String, FileMetaEntry)> = renames
+        .iter()
+        .filter_map(|(from, to)| album.files.remove(from).map(|e| (to.clone(), e)))
+        .collect();
+    album.files.extend(moved);
+    let renamed: HashMap<&str, &str> = renames
+        .iter()
+        .map(|(from, to)| (from.as_str(), to.as_str()))
+        .collect();
+    for name in album
+        .duplicates_ignore
+        .iter_mut()
+        .flatten()
+        .chain(album.properties.cover.iter_mut())
+    {
+        if let Some(to) = renamed.get(name.as_str()) {
+            *name = to.to_string();
+        }
+    }
+    write_album_meta_without_lock(dir, &album)
+}
+
+pub(crate) fn merge_album_meta_caller_holds_lock(
+    source_dir: &Path,
+    target_dir: &Path,
+    moves: &[(String, String)],
+) -> Result<(), String> {
+    let mut source_album = read_album_meta_without_lock(source_dir);
+    let mut target_album = read_album_meta_without_lock(target_dir);
+    let renamed: HashMap<&str, &str> = moves
+        .iter()
+        .map(|(src, tgt)| (src.as_str(), tgt.as_str()))
+        .collect();
+    for (source_name, target_name) in moves {
+        let entry = source_album.files.remove(source_name).unwrap_or_default();
+        insert_merged_entry(&mut target_album, target_name, entry);
+    }
+    for [a, b] in std::mem::take(&mut source_album.duplicates_ignore) {
+        let (Some(a), Some(b)) = (renamed.get(a.as_str()), renamed.get(b.as_str())) else {
+            continue;
+        };
+        let pair = if a <= b {
+            [a.to_string(), b.to_string()]
+        } else {
+            [b.to_string(), a.to_string()]
+        };
+        if !target_album.duplicates_ignore.contains(&pair) {
+            target_album.duplicates_ignore.push(pair);
+        }
+    }
+    let source_props = &source_album.properties;
+    let props = &mut target_album.properties;
+    if props.title.is_none() {
+        props.title = source_props.title.clone();
+    }
+    if props.description.is_none() {
+        props.description = source_props.description.clone();
+    }
+    if props.cover.is_none() {
+        props.cover = source_props
+            .cover
+            .as_deref()
+            .and_then(|cover| renamed.get(cover))
+            .map(|cover| cover.to_string());
+    }
+    if props.sort_key.is_none() && props.sort_dir.is_none() {
+        props.sort_key = source_props.sort_key;
+        props.sort_dir = source_props.sort_dir;
     }
     write_album_meta_without_lock(source_dir, &source_album)?;
     write_album_meta_without_lock(target_dir, &target_album)?;
     Ok(())
 }
 
+pub(crate) fn copy_media_metadata_batch(
+    source_dir: &Path,
+    target_dir: &Path,
+    copies: &[(String, String)],
+) -> Result<(), String> {
+    if copies.is_empty() {
+        return Ok(());
+    }
+    let _guard = STORE_WRITE_LOCK.lock().unwrap();
+    let source_album = read_album_meta_without_lock(source_dir);
+    let mut target_album = read_album_meta_without_lock(target_dir);
+    for (source_name, target_name) in copies {
+        if let Some(entry) = source_album.files.get(source_name) {
+            target_album
+                .files
+                .insert(target_name.clone(), entry.clone());
+        }
+    }
+    write_album_meta_without_lock(target_dir, &target_album)
+}
+
 pub(crate) fn load_album_file_hash(path: &Path) -> Option<CachedHash> {
     let dir = path.parent()?;
     let name = path.file_name()?.to_str()?;
@@ -536,16 +772,81 @@ pub fn datetime_original(p: &Path) -> Option<u64> {
         .map(|ndt| Utc.from_utc_datetime(&ndt).timestamp() as u64)
 }
 
-pub fn probe(
-    path: &str,
-    is_video: bool,
-) -> Result<(Option<u64>, Option<u32>, Option<u32>), String> {
+pub fn exif_gps(p: &Path) -> Option<[f64; 2]> {
+    let mut buf = BufReader::new(File::open(p).ok()?);
+    let exif = Reader::new().read_from_container(&mut buf).ok()?;
+    let coord = |value_tag: Tag, ref_tag: Tag, negative: u8| -> Option<f64> {
+        let field = exif.get_field(value_tag, In::PRIMARY)?;
+        let parts = match field.value {
+            Value::Rational(ref v) if v.len() >= 3 => [v[0].to_f64(), v[1].to_f64(), v[2].to_f64()],
+            _ => return None,
+        };
+        let mut deg = parts[0] + parts[1] / 60.0 + parts[2] / 3600.0;
+        if let Some(r) = exif.get_field(ref_tag, In::PRIMARY) {
+            if let Value::Ascii(ref v) = r.value {
+                if v.first().and_then(|b| b.first()) == Some(&negative) {
+                    deg = -deg;
+                }
+            }
+        }
+        deg.is_finite().then_some(deg)
+    };
+    let lat = coord(Tag::GPSLatitude, Tag::GPSLatitudeRef, b'S')?;
+    let lon = coord(Tag::GPSLongitude, Tag::GPSLongitudeRef, b'W')?;
+    valid_gps(lat, lon)
+}
+
+pub fn exif_camera_model(p: &Path) -> Option<String> {
+    let mut buf = BufReader::new(File::open(p).ok()?);
+    let exif = Reader::new().read_from_container(&mut buf).ok()?;
+    let field = exif.get_field(Tag::Model, In::PRIMARY)?;
+    let Value::Ascii(ref v) = field.value else {
+        return None;
+    };
+    let model = std::str::from_utf8(v.first()?).ok()?;
+    let model = model.trim_matches(char::from(0)).trim();
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+fn valid_gps(lat: f64, lon: f64) -> Option<[f64; 2]> {
+    let in_range = (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon);
+    let null_island = lat == 0.0 && lon == 0.0;
+    (in_range && !null_island).then_some([lat, lon])
+}
+
+pub(crate) fn parse_iso6709(value: &str) -> Option<[f64; 2]> {
+    let value = value.trim().trim_end_matches('/');
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for (i, c) in value.char_indices().skip(1) {
+        if c == '+' || c == '-' {
+            parts.push(&value[start..i]);
+            start = i;
+        }
+    }
+    parts.push(&value[start..]);
+    if parts.len() < 2 {
+        return None;
+    }
+    let lat: f64 = parts[0].parse().ok()?;
+    let lon: f64 = parts[1].parse().ok()?;
+    valid_gps(lat, lon)
+}
+
+pub struct ProbeResult {
+    pub shoot: Option<u64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub gps: Option<[f64; 2]>,
+}
+
+pub fn probe(path: &str, is_video: bool) -> Result<ProbeResult, String> {
     let settings = read_settings();
     let mut cmd = Command::new(ffmpeg_sidecar::paths::ffmpeg_path());
     cmd.args(["-i", path, "-hide_banner", "-f", "null", "-"]);
     let output = run_command_with_timeout(cmd, metadata_probe_timeout(), is_video)?;
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let (mut shoot, mut width, mut height) = (None, None, None);
+    let (mut shoot, mut width, mut height, mut gps) = (None, None, None, None);
     for line in stderr.lines() {
         if line.contains("Stream") && line.contains("Video:") {
             if let Some(pos) = line.find(", ") {
@@ -572,8 +873,103 @@ pub fn probe(
                 }
             }
         }
+        if gps.is_none() {
+            if let Some((key, value)) = line.split_once(':') {
+                let key = key.trim();
+                if key == "location" || key.ends_with("location.ISO6709") {
+                    gps = parse_iso6709(value);
+                }
+            }
+        }
+    }
+    Ok(ProbeResult {
+        shoot,
+        width,
+        height,
+        gps,
+    })
+}
+
+fn media_kind(p: &Path) -> (bool, bool) {
+    let ext_lower = p
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    (
+        crate::constants::IMAGE_EXTENSIONS.contains(&ext_lower.as_str()),
+        crate::constants::VIDEO_EXTENSIONS.contains(&ext_lower.as_str()),
+    )
+}
+
+fn scan_gps(p: &Path) -> Result<Option<[f64; 2]>, String> {
+    let (is_image, is_video) = media_kind(p);
+    if is_image {
+        return Ok(exif_gps(p));
+    }
+    if is_video {
+        return probe(&p.to_string_lossy(), true).map(|r| r.gps);
+    }
+    Ok(None)
+}
+
+pub(crate) fn backfill_gps(path: &Path) -> Result<Option<[f64; 2]>, String> {
+    let dir = path.parent().ok_or("Invalid path")?;
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid filename")?
+        .to_string();
+    let gps = scan_gps(path).unwrap_or_else(|e| {
+        log::warn!("failed to read location of {}: {}", path.display(), e);
+        None
+    });
+    update_album_meta(dir, |album| {
+        let entry = album.files.entry(name).or_default();
+        entry.gps = gps;
+        entry.gps_scanned = true;
+        Ok(())
+    })?;
+    Ok(gps)
+}
+
+static GPS_BACKFILL: Lazy<Mutex<(VecDeque<PathBuf>, HashSet<PathBuf>)>> =
+    Lazy::new(|| Mutex::new((VecDeque::new(), HashSet::new())));
+static GPS_BACKFILL_RUNNING: AtomicBool = AtomicBool::new(false);
+
+fn queue_gps_backfill(path: &Path) {
+    {
+        let mut pending = GPS_BACKFILL.lock().unwrap();
+        if !pending.1.insert(path.to_path_buf()) {
+            return;
+        }
+        pending.0.push_back(path.to_path_buf());
     }
-    Ok((shoot, width, height))
+    if GPS_BACKFILL_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    thread::spawn(|| {
+        set_low_priority_current_thread();
+        loop {
+            let next = {
+                let mut pending = GPS_BACKFILL.lock().unwrap();
+                let next = pending.0.pop_front();
+                if next.is_none() {
+                    GPS_BACKFILL_RUNNING.store(false, Ordering::SeqCst);
+                }
+                next
+            };
+            let Some(path) = next else {
+                return;
+            };
+            if path.is_file() {
+                if let Err(e) = backfill_gps(&path) {
+                    log::warn!("failed to store location of {}: {}", path.display(), e);
+                }
+            }
+            GPS_BACKFILL.lock().unwrap().1.remove(&path);
+        }
+    });
 }
 
 #[tauri::command]
@@ -591,22 +987,26 @@ pub fn get_file_metadata(path: &str) -> Result<String, String> {
         .and_then(|m| m.created().ok())
         .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
         .map(|d| d.as_secs());
-    let ext_lower = p
-        .extension()
-        .and_then(|s| s.to_str())
-        .unwrap_or("")
-        .to_ascii_lowercase();
     let mut shoot = read_embedded_timestamp(p);
-    let is_image = crate::constants::IMAGE_EXTENSIONS.contains(&ext_lower.as_str());
-    let is_video = crate::constants::VIDEO_EXTENSIONS.contains(&ext_lower.as_str());
-    let (probe_shoot, width, height) = if is_image || is_video {
+    let (is_image, is_video) = media_kind(p);
+    let probed = if is_image || is_video {
         probe(path, is_video)?
     } else {
-        (None, None, None)
+        ProbeResult {
+            shoot: None,
+            width: None,
+            height: None,
+            gps: None,
+        }
     };
     if shoot.is_none() {
-        shoot = probe_shoot;
+        shoot = probed.shoot;
     }
+    let gps = if is_image {
+        exif_gps(p).or(probed.gps)
+    } else {
+        probed.gps
+    };
     if shoot.is_none() && is_image {
         if let Some(dt) = datetime_original(p) {
             shoot = Some(dt);
@@ -617,13 +1017,15 @@ pub fn get_file_metadata(path: &str) -> Result<String, String> {
         s: shoot,
         i: is_image,
         v: is_video,
-        w: width,
-        h: height,
+        w: probed.width,
+        h: probed.height,
     };
     let packed = meta.pack();
     let mut album = read_album_meta(parent);
     let entry = album.files.entry(file_name.clone()).or_default();
     entry.meta = Some(packed.clone());
+    entry.gps = gps;
+    entry.gps_scanned = true;
     write_file_meta(parent, &file_name, entry)?;
     Ok(packed)
 }
@@ -642,6 +1044,9 @@ pub fn get_file_metadata_cached(path: &Path) -> Result<String, String> {
     let album = read_album_meta(dir);
     if let Some(entry) = album.files.get(&name) {
         if let Some(meta) = entry.meta.clone() {
+            if !entry.gps_scanned {
+                queue_gps_backfill(path);
+            }
             return Ok(meta);
         }
     }
@@ -655,6 +1060,7 @@ pub fn set_media_timestamp(
     timestamp: u64,
 ) -> Result<Vec<DetachedMediaEntry>, String> {
     let dir = PathBuf::from(&album_path);
+    ensure_online(&dir)?;
     if !dir.is_dir() {
         return Err(format!("{} is not a directory", album_path));
     }
@@ -695,6 +1101,8 @@ pub fn set_media_timestamp(
             meta: packed,
             name: name.clone(),
             favorite: if entry.favorite { Some(true) } else { None },
+            tags: entry.tags.clone(),
+            thumb: None,
         });
     }
 
@@ -704,6 +1112,7 @@ pub fn set_media_timestamp(
 #[tauri::command]
 pub fn set_media_favorite(path: String, favorite: bool) -> Result<DetachedMediaEntry, String> {
     let p = Path::new(&path);
+    ensure_online(p)?;
     if !p.exists() {
         return Err(format!("{} does not exist", path));
     }
@@ -731,5 +1140,7 @@ pub fn set_media_favorite(path: String, favorite: bool) -> Result<DetachedMediaE
         meta,
         name,
         favorite: if favorite { Some(true) } else { None },
+        tags: entry.tags.clone(),
+        thumb: None,
     })
 }
@@ -11,21 +11,32 @@ use std::{
 };
 
 use once_cell::sync::Lazy;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tauri::{AppHandle, Emitter, Wry};
 
 use crate::{
     album::walk_album_paths,
+    catalog::schedule_catalog_refresh,
     constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
     duplicates::compute_hash_for_path,
+    library::library_overrides,
     metadata::{
         clear_thumb_failed, get_file_metadata_cached, is_meta_failed, is_thumb_failed,
         load_album_file_hash, mark_hash_failed, mark_meta_failed, mark_thumb_failed,
         read_album_meta, AlbumMeta, FileMetaEntry,
     },
-    settings::{read_settings, HashConfig},
-    thumb::{ensure_thumb_with_settings, thumb_path},
+    preload_journal::{
+        close_journal, forget_albums, mark_journal_dirty, open_journal, record_album_queued,
+        PendingWork,
+    },
+    scheduler::{
+        ensure_scheduler_monitor, refresh_scheduler, scheduler_mode, scheduler_status,
+        throttle_if_needed, SchedulerMode, SchedulerStatus,
+    },
+    settings::{read_settings, read_settings_for, AdvancedSettings, HashConfig},
+    thumb::{ensure_thumb_with_settings, sweep_stale_thumb_locks, thumb_path},
+    upload::sweep_stale_uploads,
     util::{has_extension, heic_to_jpeg, newer_than, set_low_priority_current_thread},
 };
 
@@ -90,7 +101,14 @@ static ALLOW_OPEN: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
 static THUMB_WORKER_COUNTER: Lazy<AtomicUsize> = Lazy::new(|| AtomicUsize::new(0));
 static META_WORKER_COUNTER: Lazy<AtomicUsize> = Lazy::new(|| AtomicUsize::new(0));
 static HASH_WORKER_COUNTER: Lazy<AtomicUsize> = Lazy::new(|| AtomicUsize::new(0));
-static ACTIVE_ROOT: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+static OPEN_ROOTS: Lazy<Mutex<Vec<PathBuf>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static FOCUSED_ROOT: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+static PINNED_ROOTS: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+static CURRENT_PRELOAD_DIR: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+static PRIORITY_ALBUMS: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+static PRELOAD_PAUSED: AtomicBool = AtomicBool::new(false);
+static PAUSE_GATE: Lazy<(Mutex<()>, Condvar)> = Lazy::new(|| (Mutex::new(()), Condvar::new()));
+static IDLE_SIGNAL: Lazy<(Mutex<()>, Condvar)> = Lazy::new(|| (Mutex::new(()), Condvar::new()));
 
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum TaskPriority {
@@ -104,24 +122,54 @@ impl Default for TaskPriority {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbRequestPriority {
+    #[default]
+    Visible,
+    Offscreen,
+}
+
 struct WorkQueueState<T> {
     queue: VecDeque<(PathBuf, T)>,
+    background: VecDeque<(PathBuf, T)>,
     queued: HashSet<PathBuf>,
+    urgent: HashSet<PathBuf>,
     in_progress: HashSet<PathBuf>,
     started_workers: usize,
+    desired_workers: usize,
 }
 
 impl<T> Default for WorkQueueState<T> {
     fn default() -> Self {
         Self {
             queue: VecDeque::new(),
+            background: VecDeque::new(),
             queued: HashSet::new(),
+            urgent: HashSet::new(),
             in_progress: HashSet::new(),
             started_workers: 0,
+            desired_workers: 0,
         }
     }
 }
 
+impl<T> WorkQueueState<T> {
+    fn is_idle(&self) -> bool {
+        self.queue.is_empty() && self.background.is_empty() && self.in_progress.is_empty()
+    }
+
+    fn pending(&self) -> impl Iterator<Item = &PathBuf> {
+        self.in_progress.iter().chain(
+            self.queue
+                .iter()
+                .chain(self.background.iter())
+                .map(|(p, _)| p),
+        )
+    }
+}
+
+/// `background` holds tasks for open libraries other than the focused one.
 struct WorkQueue<T> {
     state: Mutex<WorkQueueState<T>>,
     cv: Condvar,
@@ -129,35 +177,62 @@ struct WorkQueue<T> {
 
 impl<T: Send + 'static> WorkQueue<T> {
     fn enqueue(&self, path: PathBuf, data: T, priority: TaskPriority) -> bool {
+        let background = in_background_root(&path);
         let mut state = self.state.lock().unwrap();
         if state.in_progress.contains(&path) || state.queued.contains(&path) {
             return false;
         }
-        match priority {
-            TaskPriority::High => state.queue.push_front((path.clone(), data)),
-            TaskPriority::Low => state.queue.push_back((path.clone(), data)),
+        match (priority, background) {
+            (TaskPriority::High, _) => {
+                state.queue.push_front((path.clone(), data));
+                state.urgent.insert(path.clone());
+            }
+            (TaskPriority::Low, false) => state.queue.push_back((path.clone(), data)),
+            (TaskPriority::Low, true) => state.background.push_back((path.clone(), data)),
         };
         state.queued.insert(path);
         self.cv.notify_all();
         true
     }
 
-    fn next_task_blocking(&self) -> (PathBuf, T) {
+    fn next_task_blocking(&self) -> Option<(PathBuf, T)> {
         let mut guard = self.state.lock().unwrap();
         loop {
-            if let Some((path, data)) = guard.queue.pop_front() {
+            if guard.started_workers > guard.desired_workers {
+                guard.started_workers -= 1;
+                return None;
+            }
+            if preload_halted() {
+                guard = self.cv.wait(guard).unwrap();
+                continue;
+            }
+            let next = match guard.queue.pop_front() {
+                Some(task) => Some(task),
+                None => guard.background.pop_front(),
+            };
+            if let Some((path, data)) = next {
                 guard.queued.remove(&path);
+                guard.urgent.remove(&path);
                 guard.in_progress.insert(path.clone());
-                return (path, data);
+                return Some((path, data));
             }
             guard = self.cv.wait(guard).unwrap();
         }
     }
 
+    fn next_is_urgent(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .queue
+            .front()
+            .map(|(p, _)| state.urgent.contains(p))
+            .unwrap_or(false)
+    }
+
     fn mark_done(&self, path: &Path) {
         let mut state = self.state.lock().unwrap();
         state.in_progress.remove(path);
-        let idle = state.queue.is_empty() && state.in_progress.is_empty();
+        let idle = state.is_idle();
         drop(state);
         self.cv.notify_all();
         if idle {
@@ -166,21 +241,22 @@ impl<T: Send + 'static> WorkQueue<T> {
     }
 
     fn has_work(&self) -> bool {
-        let state = self.state.lock().unwrap();
-        !state.queue.is_empty() || !state.in_progress.is_empty()
+        !self.state.lock().unwrap().is_idle()
     }
 
     fn outstanding(&self) -> usize {
         let state = self.state.lock().unwrap();
-        state.queue.len() + state.in_progress.len()
+        state.queue.len() + state.background.len() + state.in_progress.len()
     }
 
     fn trim_prefix(&self, prefix: &Path) -> usize {
         let mut state = self.state.lock().unwrap();
-        let before = state.queue.len();
+        let before = state.queue.len() + state.background.len();
         state.queue.retain(|(p, _)| !p.starts_with(prefix));
+        state.background.retain(|(p, _)| !p.starts_with(prefix));
         state.queued.retain(|p| !p.starts_with(prefix));
-        let after = state.queue.len();
+        state.urgent.retain(|p| !p.starts_with(prefix));
+        let after = state.queue.len() + state.background.len();
         let removed = before.saturating_sub(after);
         if removed > 0 {
             self.cv.notify_all();
@@ -188,10 +264,90 @@ impl<T: Send + 'static> WorkQueue<T> {
         removed
     }
 
+    fn promote_prefix(&self, prefix: &Path) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let (matching, rest): (VecDeque<_>, VecDeque<_>) = state
+            .queue
+            .drain(..)
+            .chain(state.background.drain(..))
+            .partition(|(p, _)| p.starts_with(prefix));
+        let promoted = matching.len();
+        state.queue = matching;
+        for task in rest {
+            if in_background_root(&task.0) {
+                state.background.push_back(task);
+            } else {
+                state.queue.push_back(task);
+            }
+        }
+        promoted
+    }
+
+    fn reorder(&self, paths: &[PathBuf], priority: TaskPriority) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let wanted: HashMap<&Path, usize> = paths
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.as_path(), i))
+            .collect();
+        let (mut matching, rest): (Vec<_>, Vec<_>) = state
+            .queue
+            .drain(..)
+            .partition(|(p, _)| wanted.contains_key(p.as_path()));
+        matching.sort_by_key(|(p, _)| wanted[p.as_path()]);
+        let moved = matching.len();
+        match priority {
+            TaskPriority::High => {
+                state.urgent.extend(matching.iter().map(|(p, _)| p.clone()));
+                state.queue = matching.into();
+                state.queue.extend(rest);
+            }
+            TaskPriority::Low => {
+                for (p, _) in &matching {
+                    state.urgent.remove(p);
+                }
+                state.queue = rest.into();
+                state.queue.extend(matching);
+            }
+        }
+        moved
+    }
+
+    fn wake(&self) {
+        let _state = self.state.lock().unwrap();
+        self.cv.notify_all();
+    }
+
+    fn pending_paths(&self) -> Vec<PathBuf> {
+        let state = self.state.lock().unwrap();
+        state.pending().cloned().collect()
+    }
+
+    fn pending_under(&self, root: &Path) -> usize {
+        let state = self.state.lock().unwrap();
+        state.pending().filter(|p| p.starts_with(root)).count()
+    }
+
+    fn snapshot(&self) -> QueueStatus {
+        let state = self.state.lock().unwrap();
+        QueueStatus {
+            queued: state.queue.len() + state.background.len(),
+            in_progress: state
+                .in_progress
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect(),
+            workers: state.started_workers,
+        }
+    }
+
     fn clear(&self) {
         let mut state = self.state.lock().unwrap();
         state.queue.clear();
+        state.background.clear();
         state.queued.clear();
+        state.urgent.clear();
         state.in_progress.clear();
         self.cv.notify_all();
     }
@@ -225,6 +381,7 @@ struct HashQueueState {
     in_progress_started: HashMap<PathBuf, std::time::Instant>,
     done: usize,
     started_workers: usize,
+    desired_workers: usize,
 }
 
 struct HashQueue {
@@ -237,6 +394,37 @@ static HASH_QUEUE: Lazy<HashQueue> = Lazy::new(|| HashQueue {
     cv: Condvar::new(),
 });
 
+#[derive(Serialize)]
+pub struct QueueStatus {
+    queued: usize,
+    in_progress: Vec<String>,
+    workers: usize,
+}
+
+#[derive(Clone, Serialize)]
+pub struct LibraryProgress {
+    root: String,
+    focused: bool,
+    albums_queued: usize,
+    thumbnails: usize,
+    metadata: usize,
+    hashes: usize,
+}
+
+#[derive(Serialize)]
+pub struct PreloadStatus {
+    paused: bool,
+    running: bool,
+    current_album: Option<String>,
+    albums_queued: Vec<String>,
+    albums_preloaded: usize,
+    thumbnails: QueueStatus,
+    metadata: QueueStatus,
+    hashes: QueueStatus,
+    scheduler: SchedulerStatus,
+    libraries: Vec<LibraryProgress>,
+}
+
 #[derive(Clone, Serialize)]
 struct PreloadProgressPayload {
     stage: PreloadStage,
@@ -248,6 +436,8 @@ struct PreloadProgressPayload {
     thumbnails: StageProgress,
     metadata: StageProgress,
     active_actions: usize,
+    scheduler: SchedulerStatus,
+    libraries: Vec<LibraryProgress>,
 }
 
 fn combined_progress_totals() -> (usize, usize) {
@@ -317,6 +507,8 @@ fn build_progress_payload(stage: PreloadStage) -> PreloadProgressPayload {
         thumbnails: stage_counts(PreloadStage::Thumbnails),
         metadata: stage_counts(PreloadStage::Metadata),
         active_actions: active_actions(),
+        scheduler: scheduler_status(),
+        libraries: library_progress(),
     }
 }
 
@@ -394,17 +586,175 @@ fn reset_preload_state() {
     *LAST_PROGRESS_EMIT.lock().unwrap() = None;
     THUMB_HASH_ALBUMS.lock().unwrap().clear();
     THUMB_HASH_FAILED.lock().unwrap().clear();
+    PRIORITY_ALBUMS.lock().unwrap().clear();
     PRELOAD_STAGE.store(PreloadStage::Idle.as_u8(), Ordering::SeqCst);
     emit_progress(Some(PreloadStage::Idle));
+    notify_idle_waiters();
+}
+
+fn library_progress() -> Vec<LibraryProgress> {
+    let roots = OPEN_ROOTS.lock().unwrap().clone();
+    let focused = FOCUSED_ROOT.lock().unwrap().clone();
+    let queued_albums = PRELOAD_QUEUE.lock().unwrap().clone();
+    roots
+        .into_iter()
+        .map(|root| LibraryProgress {
+            focused: focused.as_ref() == Some(&root),
+            albums_queued: queued_albums
+                .iter()
+                .filter(|d| d.starts_with(&root))
+                .count(),
+            thumbnails: THUMB_WORK.pending_under(&root),
+            metadata: META_WORK.pending_under(&root),
+            hashes: HASH_QUEUE.pending_under(&root),
+            root: root.to_string_lossy().into_owned(),
+        })
+        .collect()
+}
+
+fn in_open_roots(path: &Path) -> bool {
+    let roots = OPEN_ROOTS.lock().unwrap();
+    roots.is_empty() || roots.iter().any(|r| path.starts_with(r))
+}
+
+fn root_for(path: &Path) -> Option<PathBuf> {
+    OPEN_ROOTS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|r| path.starts_with(r))
+        .cloned()
+}
+
+/// True when another library has focus, so work under `path` should yield.
+fn in_background_root(path: &Path) -> bool {
+    FOCUSED_ROOT
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|r| !path.starts_with(r))
+        .unwrap_or(false)
+}
+
+fn is_focused(path: &Path) -> bool {
+    FOCUSED_ROOT
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|r| path.starts_with(r))
+        .unwrap_or(false)
+}
+
+pub(crate) fn open_root(root: &Path) -> bool {
+    {
+        let mut roots = OPEN_ROOTS.lock().unwrap();
+        if roots.iter().any(|r| r == root) {
+            return false;
+        }
+        roots.push(root.to_path_buf());
+    }
+    log::info!("library opened {}", root.display());
+    restore_journal(root);
+    let root = root.to_path_buf();
+    thread::spawn(move || {
+        let albums = walk_album_paths(&root).unwrap_or_default();
+        let removed: usize = albums
+            .iter()
+            .map(|album| sweep_stale_thumb_locks(&album.join(".room237-thumb")))
+            .sum();
+        for album in &albums {
+            sweep_stale_uploads(album);
+        }
+        if removed > 0 {
+            log::info!(
+                "removed {} stale thumb locks under {}",
+                removed,
+                root.display()
+            );
+        }
+    });
+    true
+}
+
+pub(crate) fn focus_root(root: &Path) {
+    {
+        let mut focused = FOCUSED_ROOT.lock().unwrap();
+        if focused.as_deref() == Some(root) {
+            return;
+        }
+        *focused = Some(root.to_path_buf());
+    }
+    THUMB_WORK.promote_prefix(root);
+    META_WORK.promote_prefix(root);
+    HASH_QUEUE.promote_prefix(root);
+    {
+        let mut q = PRELOAD_QUEUE.lock().unwrap();
+        let (focused, rest): (VecDeque<_>, VecDeque<_>) =
+            q.drain(..).partition(|d| d.starts_with(root));
+        *q = focused;
+        q.extend(rest);
+    }
+    log::info!("library focused {}", root.display());
+    emit_progress(None);
 }
 
-pub(crate) fn set_active_root(root: &Path) {
-    let mut active = ACTIVE_ROOT.lock().unwrap();
-    if active.as_ref().map(|r| r == root).unwrap_or(false) {
+/// Makes `root` the library the UI is showing.
+pub(crate) fn switch_root(root: &Path) {
+    let previous = FOCUSED_ROOT.lock().unwrap().clone();
+    open_root(root);
+    focus_root(root);
+    let Some(previous) = previous.filter(|p| p != root) else {
         return;
+    };
+    if is_root_open(&previous) && !PINNED_ROOTS.lock().unwrap().contains(&previous) {
+        close_root(&previous);
     }
-    *active = Some(root.to_path_buf());
-    reset_preload_state();
+}
+
+pub(crate) fn is_root_open(root: &Path) -> bool {
+    OPEN_ROOTS.lock().unwrap().iter().any(|r| r == root)
+}
+
+pub(crate) fn close_root(root: &Path) {
+    PINNED_ROOTS.lock().unwrap().remove(root);
+    let remaining = {
+        let mut roots = OPEN_ROOTS.lock().unwrap();
+        roots.retain(|r| r != root);
+        roots.len()
+    };
+    {
+        let mut focused = FOCUSED_ROOT.lock().unwrap();
+        if focused.as_deref() == Some(root) {
+            *focused = None;
+        }
+    }
+    close_journal(root);
+    if remaining == 0 {
+        reset_preload_state();
+        return;
+    }
+    if CURRENT_PRELOAD_DIR
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|d| d.starts_with(root))
+        .unwrap_or(false)
+    {
+        if let Some(cancel) = CURRENT_PRELOAD_CANCEL.lock().unwrap().as_ref() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    }
+    THUMB_HASH_FAILED
+        .lock()
+        .unwrap()
+        .retain(|p, _| !p.starts_with(root));
+    PRIORITY_ALBUMS
+        .lock()
+        .unwrap()
+        .retain(|p| !p.starts_with(root));
+    drop_preload_for_path(root);
+    log::info!("library closed {}", root.display());
+    try_emit_end();
 }
 
 fn emit_hash_event(kind: &str, completed: usize, total: usize) {
@@ -445,16 +795,24 @@ impl HashQueue {
         (added, done, total)
     }
 
-    fn next_task_blocking(&self) -> PathBuf {
+    fn next_task_blocking(&self) -> Option<PathBuf> {
         let mut guard = self.state.lock().unwrap();
         loop {
+            if guard.started_workers > guard.desired_workers {
+                guard.started_workers -= 1;
+                return None;
+            }
+            if preload_halted() {
+                guard = self.cv.wait(guard).unwrap();
+                continue;
+            }
             if let Some(path) = guard.queue.pop_front() {
                 guard.queued.remove(&path);
                 guard.in_progress.insert(path.clone());
                 guard
                     .in_progress_started
                     .insert(path.clone(), std::time::Instant::now());
-                return path;
+                return Some(path);
             }
             guard = self.cv.wait(guard).unwrap();
         }
@@ -499,6 +857,54 @@ impl HashQueue {
         None
     }
 
+    fn promote_prefix(&self, prefix: &Path) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let (matching, rest): (VecDeque<_>, VecDeque<_>) =
+            state.queue.drain(..).partition(|p| p.starts_with(prefix));
+        let promoted = matching.len();
+        state.queue = matching;
+        state.queue.extend(rest);
+        promoted
+    }
+
+    fn pending_paths(&self) -> Vec<PathBuf> {
+        let state = self.state.lock().unwrap();
+        state
+            .in_progress
+            .iter()
+            .chain(state.queue.iter())
+            .cloned()
+            .collect()
+    }
+
+    fn pending_under(&self, root: &Path) -> usize {
+        let state = self.state.lock().unwrap();
+        state
+            .in_progress
+            .iter()
+            .chain(state.queue.iter())
+            .filter(|p| p.starts_with(root))
+            .count()
+    }
+
+    fn wake(&self) {
+        let _state = self.state.lock().unwrap();
+        self.cv.notify_all();
+    }
+
+    fn snapshot(&self) -> QueueStatus {
+        let state = self.state.lock().unwrap();
+        QueueStatus {
+            queued: state.queue.len(),
+            in_progress: state
+                .in_progress
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect(),
+            workers: state.started_workers,
+        }
+    }
+
     fn totals(&self) -> (usize, usize) {
         let state = self.state.lock().unwrap();
         let total = state.done + state.queue.len() + state.in_progress.len();
@@ -717,7 +1123,7 @@ pub(crate) fn wait_for_album_hashes(
             "hash-wait: artifacts missing for {} – requeuing all albums under active root",
             dir.display()
         );
-        if let Some(root) = ACTIVE_ROOT.lock().unwrap().clone() {
+        if let Some(root) = root_for(dir) {
             if let Ok(albums) = walk_album_paths(&root) {
                 for album in albums {
                     drop_preload_for_path(&album);
@@ -862,6 +1268,20 @@ pub(crate) fn wait_for_album_hashes(
     }
 }
 
+fn notify_idle_waiters() {
+    let (lock, cv) = &*IDLE_SIGNAL;
+    let _guard = lock.lock().unwrap();
+    cv.notify_all();
+}
+
+fn wait_until_idle() {
+    let (lock, cv) = &*IDLE_SIGNAL;
+    let mut guard = lock.lock().unwrap();
+    while is_preloading() {
+        guard = cv.wait(guard).unwrap();
+    }
+}
+
 fn try_emit_end() {
     let thumb_running = THUMB_WORK.has_work();
     let meta_running = META_WORK.has_work();
@@ -881,22 +1301,22 @@ fn try_emit_end() {
         if !hash_running {
             schedule_thumb_hash_scan();
         }
+        for root in OPEN_ROOTS.lock().unwrap().iter() {
+            schedule_catalog_refresh(root);
+        }
     }
+    mark_journal_dirty();
+    notify_idle_waiters();
 }
 
 fn queue_album_for_hashing(dir: &Path) {
-    if let Some(root) = ACTIVE_ROOT.lock().unwrap().clone() {
-        if !dir.starts_with(&root) {
-            return;
-        }
+    if !in_open_roots(dir) {
+        return;
     }
     THUMB_HASH_ALBUMS.lock().unwrap().insert(dir.to_path_buf());
 }
 
 fn schedule_thumb_hash_scan() {
-    let settings = read_settings();
-    let hash_cfg = settings.hash_config();
-    let active_root = ACTIVE_ROOT.lock().unwrap().clone();
     let albums: Vec<PathBuf> = {
         let mut pending = THUMB_HASH_ALBUMS.lock().unwrap();
         pending.drain().collect()
@@ -907,13 +1327,15 @@ fn schedule_thumb_hash_scan() {
         return;
     }
 
-    let mut to_queue: Vec<PathBuf> = Vec::new();
+    let mut to_queue: HashMap<String, (HashConfig, Vec<PathBuf>)> = HashMap::new();
     for album in albums {
-        if let Some(root) = active_root.as_ref() {
-            if !album.starts_with(root) {
-                continue;
-            }
+        if !in_open_roots(&album) {
+            continue;
         }
+        let hash_cfg = read_settings_for(&album).hash_config();
+        let (_, album_queue) = to_queue
+            .entry(hash_cfg.hash_version.clone())
+            .or_insert_with(|| (hash_cfg, Vec::new()));
         let thumb_dir = album.join(".room237-thumb");
         if let Ok(entries) = fs::read_dir(&thumb_dir) {
             for entry in entries.flatten() {
@@ -936,13 +1358,15 @@ fn schedule_thumb_hash_scan() {
                     if !IMAGE_EXTENSIONS.contains(&ext.as_str()) {
                         continue;
                     }
-                    to_queue.push(original);
+                    album_queue.push(original);
                 }
             }
         }
     }
-    if !to_queue.is_empty() {
-        let _ = enqueue_hashes(&to_queue, &hash_cfg, TaskPriority::Low, true);
+    for (hash_cfg, paths) in to_queue.into_values() {
+        if !paths.is_empty() {
+            let _ = enqueue_hashes(&paths, &hash_cfg, TaskPriority::Low, true);
+        }
     }
 }
 
@@ -962,6 +1386,7 @@ pub(crate) fn start_preloader_worker(app: AppHandle<Wry>) {
     thread::spawn(move || {
         set_low_priority_current_thread();
         loop {
+            wait_while_paused();
             let dir_opt = {
                 let mut q = PRELOAD_QUEUE.lock().unwrap();
                 q.pop_front()
@@ -969,10 +1394,8 @@ pub(crate) fn start_preloader_worker(app: AppHandle<Wry>) {
 
             match dir_opt {
                 Some(dir) => {
-                    if let Some(root) = ACTIVE_ROOT.lock().unwrap().clone() {
-                        if !dir.starts_with(&root) {
-                            continue;
-                        }
+                    if !in_open_roots(&dir) {
+                        continue;
                     }
                     if PRELOADED.lock().unwrap().contains(&dir) {
                         continue;
@@ -981,15 +1404,17 @@ pub(crate) fn start_preloader_worker(app: AppHandle<Wry>) {
                     log::info!("preload begin {}", dir.display());
                     let cancel = Arc::new(AtomicBool::new(false));
                     *CURRENT_PRELOAD_CANCEL.lock().unwrap() = Some(cancel.clone());
+                    *CURRENT_PRELOAD_DIR.lock().unwrap() = Some(dir.clone());
 
                     let _ = preload_dir(&dir, cancel.clone(), true);
                     *CURRENT_PRELOAD_CANCEL.lock().unwrap() = None;
+                    *CURRENT_PRELOAD_DIR.lock().unwrap() = None;
 
                     if cancel.load(Ordering::Relaxed) {
                         log::info!("preload cancelled for {}", dir.display());
                         continue;
                     }
-                    PRELOADED.lock().unwrap().insert(dir.clone());
+                    mark_album_preloaded(&dir);
                 }
                 None => {
                     PRELOADER_RUNNING.store(false, Ordering::SeqCst);
@@ -1002,11 +1427,43 @@ pub(crate) fn start_preloader_worker(app: AppHandle<Wry>) {
     });
 }
 
+pub(crate) fn mark_album_preloaded(dir: &Path) {
+    PRELOADED.lock().unwrap().insert(dir.to_path_buf());
+    record_album_queued(dir);
+}
+
+pub(crate) fn pending_work_snapshot() -> PendingWork {
+    PendingWork {
+        thumbs: THUMB_WORK.pending_paths(),
+        metadata: META_WORK.pending_paths(),
+        hashes: HASH_QUEUE.pending_paths(),
+    }
+}
+
+fn restore_journal(root: &Path) {
+    let (completed, pending) = open_journal(root);
+    PRELOADED
+        .lock()
+        .unwrap()
+        .extend(completed.into_iter().filter(|dir| !artifacts_missing(dir)));
+    for p in pending.thumbs {
+        let Some(thumb_dir) = p.parent().map(|d| d.join(".room237-thumb")) else {
+            continue;
+        };
+        let _ = enqueue_thumb_task(p, thumb_dir, true, TaskPriority::Low);
+    }
+    for p in pending.metadata {
+        let _ = enqueue_meta_task(p, true, TaskPriority::Low);
+    }
+    if !pending.hashes.is_empty() {
+        let cfg = read_settings_for(root).hash_config();
+        let _ = enqueue_hashes(&pending.hashes, &cfg, TaskPriority::Low, false);
+    }
+}
+
 pub fn enqueue_preload(dir: &Path) {
-    if let Some(root) = ACTIVE_ROOT.lock().unwrap().clone() {
-        if !dir.starts_with(&root) {
-            return;
-        }
+    if !in_open_roots(dir) {
+        return;
     }
     let missing_artifacts = artifacts_missing(dir);
     {
@@ -1026,7 +1483,12 @@ pub fn enqueue_preload(dir: &Path) {
         if q.iter().any(|d| d == dir) {
             return;
         }
-        q.push_back(dir.to_path_buf());
+        if is_focused(dir) {
+            let at = q.iter().position(|d| !is_focused(d)).unwrap_or(q.len());
+            q.insert(at, dir.to_path_buf());
+        } else {
+            q.push_back(dir.to_path_buf());
+        }
         log::info!("queued {}", dir.display());
     }
 
@@ -1045,10 +1507,8 @@ pub fn preload_dir(
         log::warn!("skip preload for missing album {}", dir.display());
         return Ok(());
     }
-    if let Some(root) = ACTIVE_ROOT.lock().unwrap().clone() {
-        if !dir.starts_with(&root) {
-            return Ok(());
-        }
+    if !in_open_roots(dir) {
+        return Ok(());
     }
     let thumb_dir = dir.join(".room237-thumb");
     fs::create_dir_all(&thumb_dir).map_err(|e| e.to_string())?;
@@ -1085,6 +1545,12 @@ pub fn preload_dir(
     }
 
     let album_meta = read_album_meta(dir);
+    let thumb_version = read_settings_for(dir).thumb_version();
+    let priority = if PRIORITY_ALBUMS.lock().unwrap().remove(dir) {
+        TaskPriority::High
+    } else {
+        TaskPriority::Low
+    };
 
     for p in media.iter() {
         if cancel.load(Ordering::Relaxed) {
@@ -1096,22 +1562,21 @@ pub fn preload_dir(
             .unwrap_or("")
             .to_string();
         let cached_entry = album_meta.files.get(&fname);
-        let meta_cached = cached_entry.and_then(|e| e.meta.as_ref()).is_some();
+        let meta_cached = cached_entry
+            .map(|e| e.meta.is_some() && e.gps_scanned)
+            .unwrap_or(false);
         let thumb_fresh = thumb_path(p, &thumb_dir)
             .ok()
             .filter(|t| t.exists() && newer_than(t, p).unwrap_or(false))
-            .is_some();
+            .is_some()
+            && cached_entry.and_then(|e| e.thumb_version.as_deref())
+                == Some(thumb_version.as_str());
 
         if !thumb_fresh && !THUMB_WORK.is_tracked(p) {
-            let _ = enqueue_thumb_task(
-                p.clone(),
-                thumb_dir.clone(),
-                totals_accounted,
-                TaskPriority::Low,
-            );
+            let _ = enqueue_thumb_task(p.clone(), thumb_dir.clone(), totals_accounted, priority);
         }
         if !meta_cached && !META_WORK.is_tracked(p) {
-            let _ = enqueue_meta_task(p.clone(), totals_accounted, TaskPriority::Low);
+            let _ = enqueue_meta_task(p.clone(), totals_accounted, priority);
         }
     }
     Ok(())
@@ -1126,6 +1591,7 @@ pub fn drop_preload_for_path(prefix: &Path) {
         let mut preloaded = PRELOADED.lock().unwrap();
         preloaded.retain(|p| !p.starts_with(prefix));
     }
+    forget_albums(prefix);
     {
         let removed = THUMB_WORK.trim_prefix(prefix);
         if removed > 0 {
@@ -1147,32 +1613,31 @@ pub fn drop_preload_for_path(prefix: &Path) {
         let mut ha = THUMB_HASH_ALBUMS.lock().unwrap();
         ha.retain(|p| !p.starts_with(prefix));
     }
+    notify_idle_waiters();
 }
 
 fn start_thumb_worker() {
-    let settings = read_settings();
-    let desired = settings.preload.thumb_workers.max(1) as usize;
+    ensure_scheduler_monitor();
+    let desired = read_settings().preload.thumb_workers.max(1) as usize;
     loop {
         let worker_id = {
             let mut state = THUMB_WORK.state.lock().unwrap();
+            state.desired_workers = desired;
             if state.started_workers >= desired {
+                THUMB_WORK.cv.notify_all();
                 return;
             }
             state.started_workers += 1;
             THUMB_WORKER_COUNTER.fetch_add(1, Ordering::SeqCst) + 1
         };
-        let settings_clone = settings.clone();
         thread::spawn(move || {
             set_low_priority_current_thread();
             log::info!("thumb worker #{worker_id} start");
-            loop {
-                let (p, task) = THUMB_WORK.next_task_blocking();
-                if let Some(root) = ACTIVE_ROOT.lock().unwrap().clone() {
-                    if !p.starts_with(&root) {
-                        THUMB_WORK.mark_done(&p);
-                        emit_progress(None);
-                        continue;
-                    }
+            while let Some((p, task)) = THUMB_WORK.next_task_blocking() {
+                if !in_open_roots(&p) {
+                    THUMB_WORK.mark_done(&p);
+                    emit_progress(None);
+                    continue;
                 }
                 if is_thumb_failed(p.as_path()) {
                     THUMB_WORK.mark_done(&p);
@@ -1180,7 +1645,8 @@ fn start_thumb_worker() {
                     continue;
                 }
                 log::debug!("thumb worker #{worker_id} processing {}", p.display());
-                match ensure_thumb_with_settings(p.as_path(), &task.thumb_dir, &settings_clone) {
+                let settings = read_settings_for(&p);
+                match ensure_thumb_with_settings(p.as_path(), &task.thumb_dir, &settings) {
                     Ok(_) => {
                         let _ = clear_thumb_failed(p.as_path());
                         PRELOAD_DONE_THUMBS.fetch_add(1, Ordering::SeqCst);
@@ -1192,18 +1658,24 @@ fn start_thumb_worker() {
                 }
                 THUMB_WORK.mark_done(&p);
                 emit_progress(Some(PreloadStage::Thumbnails));
+                if !THUMB_WORK.next_is_urgent() {
+                    throttle_if_needed();
+                }
             }
+            log::info!("thumb worker #{worker_id} retired");
         });
     }
 }
 
 fn start_meta_worker() {
-    let settings = read_settings();
-    let desired = settings.preload.meta_workers.max(1) as usize;
+    ensure_scheduler_monitor();
+    let desired = read_settings().preload.meta_workers.max(1) as usize;
     loop {
         let worker_id = {
             let mut state = META_WORK.state.lock().unwrap();
+            state.desired_workers = desired;
             if state.started_workers >= desired {
+                META_WORK.cv.notify_all();
                 return;
             }
             state.started_workers += 1;
@@ -1212,14 +1684,11 @@ fn start_meta_worker() {
         thread::spawn(move || {
             set_low_priority_current_thread();
             log::debug!("meta worker #{worker_id} start");
-            loop {
-                let (p, _) = META_WORK.next_task_blocking();
-                if let Some(root) = ACTIVE_ROOT.lock().unwrap().clone() {
-                    if !p.starts_with(&root) {
-                        META_WORK.mark_done(&p);
-                        emit_progress(None);
-                        continue;
-                    }
+            while let Some((p, _)) = META_WORK.next_task_blocking() {
+                if !in_open_roots(&p) {
+                    META_WORK.mark_done(&p);
+                    emit_progress(None);
+                    continue;
                 }
                 if is_meta_failed(p.as_path()) {
                     META_WORK.mark_done(&p);
@@ -1238,34 +1707,38 @@ fn start_meta_worker() {
                 }
                 META_WORK.mark_done(&p);
                 emit_progress(Some(PreloadStage::Metadata));
+                if !META_WORK.next_is_urgent() {
+                    throttle_if_needed();
+                }
             }
+            log::debug!("meta worker #{worker_id} retired");
         });
     }
 }
 
 fn start_thumb_hash_worker() {
-    let settings = read_settings();
-    let hash_cfg = settings.hash_config();
-    let desired = settings.preload.hash_workers.max(1) as usize;
+    ensure_scheduler_monitor();
+    let desired = read_settings().preload.hash_workers.max(1) as usize;
     loop {
         let worker_id = {
             let mut state = HASH_QUEUE.state.lock().unwrap();
+            state.desired_workers = desired;
             if state.started_workers >= desired {
+                HASH_QUEUE.cv.notify_all();
                 return;
             }
             state.started_workers += 1;
             HASH_WORKER_COUNTER.fetch_add(1, Ordering::SeqCst) + 1
         };
-        let settings_clone = settings.clone();
-        let hash_cfg_clone = hash_cfg.clone();
         std::thread::spawn(move || {
             set_low_priority_current_thread();
             log::debug!("hash worker #{} start", worker_id);
-            let retry_on_thumb_change = settings_clone.preload.thumb_hash_retry_on_thumb_change;
-            let delay_ms = settings_clone.preload.thumb_hash_queue_delay_ms;
             let mut processed: u64 = 0;
-            loop {
-                let original = HASH_QUEUE.next_task_blocking();
+            while let Some(original) = HASH_QUEUE.next_task_blocking() {
+                let settings_clone = read_settings_for(&original);
+                let hash_cfg_clone = settings_clone.hash_config();
+                let retry_on_thumb_change = settings_clone.preload.thumb_hash_retry_on_thumb_change;
+                let delay_ms = settings_clone.preload.thumb_hash_queue_delay_ms;
                 processed = processed.saturating_add(1);
                 let log_this = processed <= 20 || processed % 100 == 0;
                 if log_this {
@@ -1370,7 +1843,9 @@ fn start_thumb_hash_worker() {
                 if delay_ms > 0 {
                     std::thread::sleep(Duration::from_millis(delay_ms));
                 }
+                throttle_if_needed();
             }
+            log::debug!("hash worker #{} retired", worker_id);
         });
     }
 }
@@ -1393,10 +1868,7 @@ pub fn get_preload_progress() -> usize {
 
 #[tauri::command]
 pub async fn lock_until_preloaded(app: tauri::AppHandle) -> Result<bool, String> {
-    if PRELOADER_RUNNING.load(Ordering::SeqCst)
-        || THUMB_WORK.outstanding() > 0
-        || META_WORK.outstanding() > 0
-    {
+    if is_preloading() {
         log::info!("waiting for preloader to finish");
         {
             let mut handle = PRELOAD_APP.lock().unwrap();
@@ -1404,13 +1876,10 @@ pub async fn lock_until_preloaded(app: tauri::AppHandle) -> Result<bool, String>
                 *handle = Some(app.clone());
             }
         }
-        while PRELOADER_RUNNING.load(Ordering::SeqCst)
-            || THUMB_WORK.outstanding() > 0
-            || META_WORK.outstanding() > 0
-        {
-            emit_progress(None);
-            thread::sleep(Duration::from_millis(100));
-        }
+        emit_progress(None);
+        tauri::async_runtime::spawn_blocking(wait_until_idle)
+            .await
+            .map_err(|e| e.to_string())?;
         log::info!("preloader finished");
     }
     Ok(true)
@@ -1427,3 +1896,310 @@ pub fn set_allow_open(app: AppHandle<Wry>, allow: bool) {
         }
     }
 }
+
+fn wait_while_paused() {
+    let (lock, cv) = &*PAUSE_GATE;
+    let mut guard = lock.lock().unwrap();
+    while preload_halted() {
+        guard = cv.wait(guard).unwrap();
+    }
+}
+
+fn preload_halted() -> bool {
+    PRELOAD_PAUSED.load(Ordering::SeqCst) || scheduler_mode() == SchedulerMode::Pause
+}
+
+fn wake_all_workers() {
+    {
+        let (lock, cv) = &*PAUSE_GATE;
+        let _guard = lock.lock().unwrap();
+        cv.notify_all();
+    }
+    THUMB_WORK.wake();
+    META_WORK.wake();
+    HASH_QUEUE.wake();
+}
+
+pub(crate) fn scheduler_changed() {
+    wake_all_workers();
+    emit_progress(None);
+}
+
+fn set_preload_paused(paused: bool) {
+    {
+        let (lock, _) = &*PAUSE_GATE;
+        let _guard = lock.lock().unwrap();
+        PRELOAD_PAUSED.store(paused, Ordering::SeqCst);
+    }
+    wake_all_workers();
+    log::info!("preload {}", if paused { "paused" } else { "resumed" });
+    emit_progress(None);
+}
+
+#[tauri::command]
+pub fn pause_preload() {
+    set_preload_paused(true);
+}
+
+#[tauri::command]
+pub fn resume_preload() {
+    set_preload_paused(false);
+}
+
+#[tauri::command]
+pub fn cancel_preload(album: String) -> Result<(), String> {
+    let dir = PathBuf::from(&album);
+    if CURRENT_PRELOAD_DIR.lock().unwrap().as_deref() == Some(dir.as_path()) {
+        if let Some(cancel) = CURRENT_PRELOAD_CANCEL.lock().unwrap().as_ref() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    }
+    PRIORITY_ALBUMS.lock().unwrap().remove(&dir);
+    drop_preload_for_path(&dir);
+    log::info!("preload cancelled for {}", dir.display());
+    try_emit_end();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn prioritize_album(app: AppHandle<Wry>, album: String) -> Result<(), String> {
+    let dir = PathBuf::from(&album);
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", album));
+    }
+    if !in_open_roots(&dir) {
+        return Err(format!("{} is outside the open libraries", album));
+    }
+    let thumbs = THUMB_WORK.promote_prefix(&dir);
+    let metas = META_WORK.promote_prefix(&dir);
+    let hashes = HASH_QUEUE.promote_prefix(&dir);
+
+    let needs_scan = artifacts_missing(&dir) || !PRELOADED.lock().unwrap().contains(&dir);
+    if needs_scan {
+        PRIORITY_ALBUMS.lock().unwrap().insert(dir.clone());
+        let mut q = PRELOAD_QUEUE.lock().unwrap();
+        q.retain(|d| d != &dir);
+        q.push_front(dir.clone());
+    }
+    log::info!(
+        "prioritized {}: thumbs={} meta={} hashes={} rescan={}",
+        dir.display(),
+        thumbs,
+        metas,
+        hashes,
+        needs_scan
+    );
+    if needs_scan {
+        start_preloader_worker(app);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_preload_status() -> PreloadStatus {
+    PreloadStatus {
+        paused: PRELOAD_PAUSED.load(Ordering::SeqCst),
+        running: is_preloading(),
+        current_album: CURRENT_PRELOAD_DIR
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|p| p.to_string_lossy().into_owned()),
+        albums_queued: PRELOAD_QUEUE
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect(),
+        albums_preloaded: PRELOADED.lock().unwrap().len(),
+        thumbnails: THUMB_WORK.snapshot(),
+        metadata: META_WORK.snapshot(),
+        hashes: HASH_QUEUE.snapshot(),
+        scheduler: scheduler_status(),
+        libraries: library_progress(),
+    }
+}
+
+#[tauri::command]
+pub fn request_thumbnails(
+    paths: Vec<String>,
+    priority: Option<ThumbRequestPriority>,
+) -> Result<usize, String> {
+    let paths: Vec<PathBuf> = paths
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|p| in_open_roots(p))
+        .collect();
+    if paths.is_empty() {
+        return Ok(0);
+    }
+
+    match priority.unwrap_or_default() {
+        ThumbRequestPriority::Offscreen => Ok(THUMB_WORK.reorder(&paths, TaskPriority::Low)),
+        ThumbRequestPriority::Visible => {
+            let mut added = 0;
+            for p in paths.iter().rev() {
+                if THUMB_WORK.is_tracked(p) || !p.is_file() {
+                    continue;
+                }
+                let Some(thumb_dir) = p.parent().map(|d| d.join(".room237-thumb")) else {
+                    continue;
+                };
+                let thumb_fresh = thumb_path(p, &thumb_dir)
+                    .ok()
+                    .filter(|t| t.exists() && newer_than(t, p).unwrap_or(false))
+                    .is_some();
+                if thumb_fresh {
+                    continue;
+                }
+                if fs::create_dir_all(&thumb_dir).is_err() {
+                    continue;
+                }
+                if enqueue_thumb_task(p.clone(), thumb_dir, true, TaskPriority::High) {
+                    added += 1;
+                }
+            }
+            Ok(THUMB_WORK.reorder(&paths, TaskPriority::High).max(added))
+        }
+    }
+}
+
+pub(crate) fn apply_settings_change(previous: &AdvancedSettings, next: &AdvancedSettings) {
+    refresh_scheduler();
+    if previous.preload.thumb_workers != next.preload.thumb_workers
+        && THUMB_WORK.state.lock().unwrap().started_workers > 0
+    {
+        start_thumb_worker();
+    }
+    if previous.preload.meta_workers != next.preload.meta_workers
+        && META_WORK.state.lock().unwrap().started_workers > 0
+    {
+        start_meta_worker();
+    }
+    if previous.preload.hash_workers != next.preload.hash_workers
+        && HASH_QUEUE.state.lock().unwrap().started_workers > 0
+    {
+        start_thumb_hash_worker();
+    }
+
+    let roots = OPEN_ROOTS.lock().unwrap().clone();
+    for root in roots {
+        let overrides = library_overrides(&root).unwrap_or_default();
+        apply_root_settings_change(
+            &root,
+            &previous.with_overrides(&overrides),
+            &next.with_overrides(&overrides),
+        );
+    }
+}
+
+pub(crate) fn apply_root_settings_change(
+    root: &Path,
+    previous: &AdvancedSettings,
+    next: &AdvancedSettings,
+) {
+    if !is_root_open(root) {
+        return;
+    }
+    let thumbs_changed = previous.thumb_version() != next.thumb_version();
+    let hashes_changed = previous.hash_config().hash_version != next.hash_config().hash_version;
+    if !thumbs_changed && !hashes_changed {
+        return;
+    }
+    // Walking a large library can take a while; keep it off the command thread.
+    let root = root.to_path_buf();
+    thread::spawn(move || {
+        let albums = walk_album_paths(&root).unwrap_or_default();
+
+        if hashes_changed {
+            log::info!("hash version changed, requeueing {} albums", albums.len());
+            THUMB_HASH_FAILED
+                .lock()
+                .unwrap()
+                .retain(|p, _| !p.starts_with(&root));
+            THUMB_HASH_ALBUMS
+                .lock()
+                .unwrap()
+                .extend(albums.iter().cloned());
+        }
+        if thumbs_changed {
+            log::info!("thumb version changed, requeueing {} albums", albums.len());
+            PRELOADED.lock().unwrap().retain(|d| !d.starts_with(&root));
+            forget_albums(&root);
+            {
+                let mut q = PRELOAD_QUEUE.lock().unwrap();
+                for album in albums {
+                    if !q.contains(&album) {
+                        q.push_back(album);
+                    }
+                }
+            }
+            let app = PRELOAD_APP.lock().unwrap().clone();
+            if let Some(app) = app {
+                start_preloader_worker(app);
+                return;
+            }
+        }
+        if !is_preloading() {
+            schedule_thumb_hash_scan();
+        }
+    });
+}
+
+fn library_root(root_dir: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(root_dir);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    Ok(root)
+}
+
+#[tauri::command]
+pub async fn open_library(
+    app: AppHandle<Wry>,
+    root_dir: String,
+    focus: Option<bool>,
+) -> Result<(), String> {
+    let root = library_root(&root_dir)?;
+    PINNED_ROOTS.lock().unwrap().insert(root.clone());
+    let opened = open_root(&root);
+    if focus.unwrap_or(false) {
+        focus_root(&root);
+    }
+    if !opened {
+        return Ok(());
+    }
+    let albums = tauri::async_runtime::spawn_blocking(move || walk_album_paths(&root))
+        .await
+        .map_err(|e| e.to_string())??;
+    for album in albums {
+        enqueue_preload(&album);
+    }
+    start_preloader_worker(app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn close_library(root_dir: String) -> Result<(), String> {
+    let root = PathBuf::from(&root_dir);
+    if !OPEN_ROOTS.lock().unwrap().contains(&root) {
+        return Err(format!("{} is not open", root.display()));
+    }
+    close_root(&root);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn focus_library(root_dir: String) -> Result<(), String> {
+    let root = PathBuf::from(&root_dir);
+    if !OPEN_ROOTS.lock().unwrap().contains(&root) {
+        return Err(format!("{} is not open", root.display()));
+    }
+    focus_root(&root);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_open_libraries() -> Vec<LibraryProgress> {
+    library_progress()
+}
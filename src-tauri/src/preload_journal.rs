@@ -0,0 +1,296 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    metadata::root_meta_file, preload::pending_work_snapshot, settings::read_settings_for,
+};
+
+const JOURNAL_FILE: &str = "preload.json";
+const JOURNAL_VERSION: u32 = 2;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalFile {
+    version: u32,
+    #[serde(default)]
+    thumb_version: String,
+    #[serde(default)]
+    albums: HashMap<String, u64>,
+    #[serde(default)]
+    pending: JournalPending,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct JournalPending {
+    #[serde(default)]
+    thumbs: Vec<String>,
+    #[serde(default)]
+    metadata: Vec<String>,
+    #[serde(default)]
+    hashes: Vec<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct PendingWork {
+    pub thumbs: Vec<PathBuf>,
+    pub metadata: Vec<PathBuf>,
+    pub hashes: Vec<PathBuf>,
+}
+
+struct JournalState {
+    albums: HashMap<PathBuf, u64>,
+    awaiting: HashSet<PathBuf>,
+    dirty: bool,
+}
+
+static JOURNALS: Lazy<Mutex<HashMap<PathBuf, JournalState>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static FLUSHER_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn journal_path(root: &Path) -> PathBuf {
+    root_meta_file(root, JOURNAL_FILE)
+}
+
+/// Fingerprint of an album's visible files (name, size and mtime).
+fn content_stamp(dir: &Path) -> Option<u64> {
+    let mut files: Vec<(String, u64, u128)> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') {
+                return None;
+            }
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            let mtime = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            Some((name, meta.len(), mtime))
+        })
+        .collect();
+    files.sort();
+    let mut hasher = Sha256::new();
+    for (name, len, mtime) in files {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(len.to_le_bytes());
+        hasher.update(mtime.to_le_bytes());
+    }
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    Some(u64::from_le_bytes(bytes).max(1))
+}
+
+fn to_relative(root: &Path, path: &Path) -> Option<String> {
+    path.strip_prefix(root)
+        .ok()
+        .map(|p| p.to_string_lossy().into_owned())
+}
+
+fn to_absolute(root: &Path, relative: &[String]) -> Vec<PathBuf> {
+    relative
+        .iter()
+        .map(|r| root.join(r))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+pub(crate) fn open_journal(root: &Path) -> (Vec<PathBuf>, PendingWork) {
+    let file: JournalFile = fs::read(journal_path(root))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .filter(|f: &JournalFile| f.version == JOURNAL_VERSION)
+        .unwrap_or_default();
+
+    let thumbs_current = file.thumb_version == read_settings_for(root).thumb_version();
+    let mut albums = HashMap::new();
+    if thumbs_current {
+        for (relative, stamp) in &file.albums {
+            let dir = root.join(relative);
+            if content_stamp(&dir) == Some(*stamp) {
+                albums.insert(dir, *stamp);
+            }
+        }
+    }
+    let pending = PendingWork {
+        thumbs: to_absolute(root, &file.pending.thumbs),
+        metadata: to_absolute(root, &file.pending.metadata),
+        hashes: to_absolute(root, &file.pending.hashes),
+    };
+    log::info!(
+        "preload journal for {}: {} of {} albums unchanged, pending thumbs={} meta={} hashes={}",
+        root.display(),
+        albums.len(),
+        file.albums.len(),
+        pending.thumbs.len(),
+        pending.metadata.len(),
+        pending.hashes.len()
+    );
+
+    let completed = albums.keys().cloned().collect();
+    JOURNALS.lock().unwrap().insert(
+        root.to_path_buf(),
+        JournalState {
+            albums,
+            awaiting: HashSet::new(),
+            dirty: false,
+        },
+    );
+    start_flusher();
+    (completed, pending)
+}
+
+/// Notes that every task for `dir` has been queued.
+pub(crate) fn record_album_queued(dir: &Path) {
+    let mut journals = JOURNALS.lock().unwrap();
+    if let Some((_, state)) = journals.iter_mut().find(|(root, _)| dir.starts_with(root)) {
+        state.albums.remove(dir);
+        state.awaiting.insert(dir.to_path_buf());
+    }
+}
+
+fn settle_drained_albums(pending: &PendingWork) {
+    let busy: HashSet<&Path> = pending
+        .thumbs
+        .iter()
+        .chain(pending.metadata.iter())
+        .filter_map(|p| p.parent())
+        .collect();
+    let drained: Vec<PathBuf> = {
+        let mut journals = JOURNALS.lock().unwrap();
+        journals
+            .values_mut()
+            .flat_map(|state| {
+                let (drained, waiting) = std::mem::take(&mut state.awaiting)
+                    .into_iter()
+                    .partition(|dir| !busy.contains(dir.as_path()));
+                state.awaiting = waiting;
+                drained
+            })
+            .collect::<Vec<_>>()
+    };
+    let stamped: Vec<(PathBuf, u64)> = drained
+        .into_iter()
+        .filter_map(|dir| content_stamp(&dir).map(|stamp| (dir, stamp)))
+        .collect();
+    if stamped.is_empty() {
+        return;
+    }
+    let mut journals = JOURNALS.lock().unwrap();
+    for (dir, stamp) in stamped {
+        if let Some((_, state)) = journals.iter_mut().find(|(root, _)| dir.starts_with(root)) {
+            state.albums.insert(dir, stamp);
+            state.dirty = true;
+        }
+    }
+}
+
+pub(crate) fn forget_albums(prefix: &Path) {
+    for state in JOURNALS.lock().unwrap().values_mut() {
+        state.albums.retain(|dir, _| !dir.starts_with(prefix));
+        state.awaiting.retain(|dir| !dir.starts_with(prefix));
+        state.dirty = true;
+    }
+}
+
+pub(crate) fn mark_journal_dirty() {
+    for state in JOURNALS.lock().unwrap().values_mut() {
+        state.dirty = true;
+    }
+}
+
+fn write_journal(root: &Path, albums: HashMap<String, u64>, pending: &PendingWork) {
+    let relative = |paths: &[PathBuf]| -> Vec<String> {
+        paths.iter().filter_map(|p| to_relative(root, p)).collect()
+    };
+    let file = JournalFile {
+        version: JOURNAL_VERSION,
+        thumb_version: read_settings_for(root).thumb_version(),
+        albums,
+        pending: JournalPending {
+            thumbs: relative(&pending.thumbs),
+            metadata: relative(&pending.metadata),
+            hashes: relative(&pending.hashes),
+        },
+    };
+    let path = journal_path(root);
+    let tmp = path.with_extension("json.tmp");
+    let result = serde_json::to_vec(&file)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            fs::write(&tmp, bytes).map_err(|e| e.to_string())
+        })
+        .and_then(|_| fs::rename(&tmp, &path).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        log::warn!("failed to write preload journal {}: {}", path.display(), e);
+        let _ = fs::remove_file(&tmp);
+        if let Some(state) = JOURNALS.lock().unwrap().get_mut(root) {
+            state.dirty = true;
+        }
+    }
+}
+
+fn take_dirty(root: Option<&Path>) -> Vec<(PathBuf, HashMap<String, u64>)> {
+    let mut journals = JOURNALS.lock().unwrap();
+    journals
+        .iter_mut()
+        .filter(|(r, state)| state.dirty && root.map(|root| root == r.as_path()).unwrap_or(true))
+        .map(|(root, state)| {
+            state.dirty = false;
+            let albums = state
+                .albums
+                .iter()
+                .filter_map(|(dir, stamp)| to_relative(root, dir).map(|r| (r, *stamp)))
+                .collect();
+            (root.clone(), albums)
+        })
+        .collect()
+}
+
+pub(crate) fn flush_journal() {
+    let pending = pending_work_snapshot();
+    settle_drained_albums(&pending);
+    for (root, albums) in take_dirty(None) {
+        write_journal(&root, albums, &pending);
+    }
+}
+
+pub(crate) fn close_journal(root: &Path) {
+    let pending = pending_work_snapshot();
+    settle_drained_albums(&pending);
+    for (root, albums) in take_dirty(Some(root)) {
+        write_journal(&root, albums, &pending);
+    }
+    JOURNALS.lock().unwrap().remove(root);
+}
+
+fn start_flusher() {
+    if FLUSHER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    thread::spawn(|| loop {
+        thread::sleep(FLUSH_INTERVAL);
+        flush_journal();
+    });
+}
@@ -0,0 +1,434 @@
+use std::{
+    fs,
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+use exif::{experimental::Writer, Context, Field, In, Reader, Tag};
+use ffmpeg_sidecar::command::FfmpegCommand;
+use rayon::prelude::*;
+use serde::Serialize;
+use tauri::async_runtime;
+
+use crate::{
+    album::next_available_name,
+    constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
+    metadata::{exif_gps, probe},
+    settings::{read_settings, PrivacyExportProfile},
+    util::{apply_ffmpeg_tuning, has_extension, transcode_timeout, wait_with_cancel},
+};
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_EXTENSION_HEADER: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+const SERIAL_TAGS: [Tag; 4] = [
+    Tag::BodySerialNumber,
+    Tag::LensSerialNumber,
+    Tag::ImageUniqueID,
+    Tag::MakerNote,
+];
+const OWNER_TAGS: [Tag; 2] = [Tag::CameraOwnerName, Tag::Artist];
+const VIDEO_SERIAL_KEYS: [&str; 4] = [
+    "serial_number",
+    "camera_serial_number",
+    "com.apple.quicktime.camera.identifier",
+    "com.android.serial",
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateExportResult {
+    pub source: String,
+    pub target: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocatedMedia {
+    pub name: String,
+    pub gps: Option<[f64; 2]>,
+    pub sources: Vec<String>,
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+fn keep_exif_field(field: &Field, profile: &PrivacyExportProfile) -> bool {
+    if field.ifd_num != In::PRIMARY {
+        return false;
+    }
+    let Tag(ctx, _) = field.tag;
+    !(profile.strip_location && ctx == Context::Gps
+        || profile.strip_serial_numbers && SERIAL_TAGS.contains(&field.tag)
+        || profile.strip_owner && OWNER_TAGS.contains(&field.tag))
+}
+
+fn scrub_tiff(raw: &[u8], profile: &PrivacyExportProfile) -> Result<Option<Vec<u8>>, String> {
+    if profile.strip_all {
+        return Ok(None);
+    }
+    let exif = Reader::new()
+        .read_raw(raw.to_vec())
+        .map_err(|e| format!("Failed to parse EXIF: {e}"))?;
+    let fields: Vec<&Field> = exif
+        .fields()
+        .filter(|f| keep_exif_field(f, profile))
+        .collect();
+    if fields.is_empty() {
+        return Ok(None);
+    }
+    let mut writer = Writer::new();
+    for field in fields {
+        writer.push_field(field);
+    }
+    let mut out = Cursor::new(Vec::new());
+    writer
+        .write(&mut out, exif.little_endian())
+        .map_err(|e| format!("Failed to write EXIF: {e}"))?;
+    Ok(Some(out.into_inner()))
+}
+
+fn keep_xmp(packet: &[u8], profile: &PrivacyExportProfile) -> bool {
+    if profile.strip_all || profile.strip_xmp {
+        return false;
+    }
+    let has_location = contains(packet, b"GPSLatitude") || contains(packet, b"GPSLongitude");
+    let has_serial = contains(packet, b"SerialNumber");
+    let has_owner = contains(packet, b"dc:creator") || contains(packet, b"OwnerName");
+    !(profile.strip_location && has_location
+        || profile.strip_serial_numbers && has_serial
+        || profile.strip_owner && has_owner)
+}
+
+fn push_jpeg_segment(out: &mut Vec<u8>, marker: u8, parts: &[&[u8]]) -> Result<(), String> {
+    let len: usize = parts.iter().map(|p| p.len()).sum::<usize>() + 2;
+    if len > u16::MAX as usize {
+        return Err("Metadata segment is too large".to_string());
+    }
+    out.extend_from_slice(&[0xFF, marker]);
+    out.extend_from_slice(&(len as u16).to_be_bytes());
+    for part in parts {
+        out.extend_from_slice(part);
+    }
+    Ok(())
+}
+
+fn scrub_jpeg(data: &[u8], profile: &PrivacyExportProfile) -> Result<Vec<u8>, String> {
+    if data.len() < 4 || data[0..2] != [0xFF, 0xD8] {
+        return Err("Not a JPEG file".to_string());
+    }
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[0..2]);
+    let mut pos = 2;
+    loop {
+        if pos + 2 > data.len() {
+            return Err("Truncated JPEG".to_string());
+        }
+        if data[pos] != 0xFF {
+            return Err("Malformed JPEG segment".to_string());
+        }
+        let marker = data[pos + 1];
+        match marker {
+            0xFF => {
+                pos += 1;
+                continue;
+            }
+            0xDA | 0xD9 => {
+                out.extend_from_slice(&data[pos..]);
+                return Ok(out);
+            }
+            0x01 | 0xD0..=0xD7 => {
+                out.extend_from_slice(&data[pos..pos + 2]);
+                pos += 2;
+                continue;
+            }
+            _ => {}
+        }
+        if pos + 4 > data.len() {
+            return Err("Truncated JPEG".to_string());
+        }
+        let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + len;
+        if len < 2 || end > data.len() {
+            return Err("Truncated JPEG segment".to_string());
+        }
+        let payload = &data[pos + 4..end];
+        match marker {
+            0xE1 if payload.starts_with(EXIF_HEADER) => {
+                if let Some(tiff) = scrub_tiff(&payload[EXIF_HEADER.len()..], profile)? {
+                    push_jpeg_segment(&mut out, marker, &[EXIF_HEADER, &tiff])?;
+                }
+            }
+            0xE1 if payload.starts_with(XMP_HEADER)
+                || payload.starts_with(XMP_EXTENSION_HEADER) =>
+            {
+                if keep_xmp(payload, profile) {
+                    out.extend_from_slice(&data[pos..end]);
+                }
+            }
+            0xED if profile.strip_owner || profile.strip_all => {}
+            0xFE if profile.strip_all => {}
+            _ => out.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+    }
+}
+
+fn scrub_webp(data: &[u8], profile: &PrivacyExportProfile) -> Result<Vec<u8>, String> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return Err("Not a WebP file".to_string());
+    }
+    let mut chunks: Vec<([u8; 4], Vec<u8>)> = Vec::new();
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let fourcc: [u8; 4] = data[pos..pos + 4].try_into().unwrap();
+        let size = u32::from_le_bytes(data[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        let start = pos + 8;
+        let end = start + size;
+        if end > data.len() {
+            return Err("Truncated WebP chunk".to_string());
+        }
+        let payload = &data[start..end];
+        match &fourcc {
+            b"EXIF" => {
+                let tiff = payload.strip_prefix(EXIF_HEADER).unwrap_or(payload);
+                if let Some(scrubbed) = scrub_tiff(tiff, profile)? {
+                    chunks.push((fourcc, scrubbed));
+                }
+            }
+            b"XMP " => {
+                if keep_xmp(payload, profile) {
+                    chunks.push((fourcc, payload.to_vec()));
+                }
+            }
+            _ => chunks.push((fourcc, payload.to_vec())),
+        }
+        pos = end + (size & 1);
+    }
+
+    let has_exif = chunks.iter().any(|(c, _)| c == b"EXIF");
+    let has_xmp = chunks.iter().any(|(c, _)| c == b"XMP ");
+    let mut body = b"WEBP".to_vec();
+    for (fourcc, mut payload) in chunks {
+        if &fourcc == b"VP8X" && !payload.is_empty() {
+            if !has_exif {
+                payload[0] &= !0x08;
+            }
+            if !has_xmp {
+                payload[0] &= !0x04;
+            }
+        }
+        body.extend_from_slice(&fourcc);
+        body.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        body.extend_from_slice(&payload);
+        if payload.len() % 2 == 1 {
+            body.push(0);
+        }
+    }
+    let mut out = b"RIFF".to_vec();
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+fn ffmpeg_private_copy(
+    input: &Path,
+    output: &Path,
+    profile: &PrivacyExportProfile,
+    is_video: bool,
+    cancelled: &dyn Fn() -> bool,
+) -> Result<(), String> {
+    let mut cmd = FfmpegCommand::new();
+    apply_ffmpeg_tuning(&mut cmd, is_video);
+    cmd.input(input.to_string_lossy()).arg("-y");
+    if is_video {
+        cmd.args(["-map", "0:v", "-map", "0:a?", "-c", "copy"]);
+    }
+    if profile.strip_all || !is_video {
+        cmd.args(["-map_metadata", "-1"]);
+    } else {
+        cmd.args(["-map_metadata", "0"]);
+        if profile.strip_location {
+            cmd.args([
+                "-map_metadata:s",
+                "-1",
+                "-metadata",
+                "location=",
+                "-metadata",
+                "location-eng=",
+                "-metadata",
+                "com.apple.quicktime.location.ISO6709=",
+            ]);
+        }
+        if profile.strip_owner {
+            cmd.args(["-metadata", "artist=", "-metadata", "author="]);
+        }
+        if profile.strip_serial_numbers {
+            for key in VIDEO_SERIAL_KEYS {
+                cmd.args(["-metadata".to_string(), format!("{key}=")]);
+            }
+        }
+    }
+    let mut child = cmd
+        .output(output.to_string_lossy())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let status =
+        wait_with_cancel(&mut child, transcode_timeout(input), cancelled).inspect_err(|_| {
+            let _ = fs::remove_file(output);
+        })?;
+    if !status.success() {
+        let _ = fs::remove_file(output);
+        return Err(format!("ffmpeg failed for {}", input.display()));
+    }
+    Ok(())
+}
+
+pub(crate) fn write_private_copy(
+    input: &Path,
+    output: &Path,
+    profile: &PrivacyExportProfile,
+) -> Result<(), String> {
+    write_private_copy_cancellable(input, output, profile, &|| false)
+}
+
+pub(crate) fn write_private_copy_cancellable(
+    input: &Path,
+    output: &Path,
+    profile: &PrivacyExportProfile,
+    cancelled: &dyn Fn() -> bool,
+) -> Result<(), String> {
+    let ext = input
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => {
+            let data = fs::read(input).map_err(|e| e.to_string())?;
+            fs::write(output, scrub_jpeg(&data, profile)?).map_err(|e| e.to_string())
+        }
+        "webp" => {
+            let data = fs::read(input).map_err(|e| e.to_string())?;
+            fs::write(output, scrub_webp(&data, profile)?).map_err(|e| e.to_string())
+        }
+        "png" => ffmpeg_private_copy(input, output, profile, false, cancelled),
+        _ if VIDEO_EXTENSIONS.contains(&ext.as_str()) => {
+            ffmpeg_private_copy(input, output, profile, true, cancelled)
+        }
+        _ => Err(format!(
+            "Cannot strip metadata from .{} files",
+            if ext.is_empty() { "?" } else { &ext }
+        )),
+    }
+}
+
+fn xmp_has_location(path: &Path) -> bool {
+    fs::read(path)
+        .map(|data| contains(&data, b"<x:xmpmeta") && contains(&data, b"GPSLatitude"))
+        .unwrap_or(false)
+}
+
+fn locate_media(path: &Path) -> Option<LocatedMedia> {
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    let mut sources = Vec::new();
+    let mut gps = None;
+    if has_extension(path, IMAGE_EXTENSIONS) {
+        if let Some(found) = exif_gps(path) {
+            gps = Some(found);
+            sources.push("exif".to_string());
+        }
+        if xmp_has_location(path) {
+            sources.push("xmp".to_string());
+        }
+    } else if has_extension(path, VIDEO_EXTENSIONS) {
+        match probe(&path.to_string_lossy(), true) {
+            Ok(probed) => {
+                if let Some(found) = probed.gps {
+                    gps = Some(found);
+                    sources.push("quicktime".to_string());
+                }
+            }
+            Err(e) => log::warn!("failed to probe {}: {}", path.display(), e),
+        }
+    }
+    (!sources.is_empty()).then_some(LocatedMedia { name, gps, sources })
+}
+
+#[tauri::command]
+pub async fn export_private_copies(
+    paths: Vec<String>,
+    target_dir: String,
+) -> Result<Vec<PrivateExportResult>, String> {
+    let target = PathBuf::from(&target_dir);
+    if !target.is_dir() {
+        return Err(format!("{} is not a directory", target_dir));
+    }
+    let profile = read_settings().privacy.export_profile;
+    async_runtime::spawn_blocking(move || {
+        let results: Vec<PrivateExportResult> = paths
+            .into_iter()
+            .map(|source| {
+                let input = PathBuf::from(&source);
+                let outcome = input
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .ok_or_else(|| "Invalid filename".to_string())
+                    .and_then(|name| next_available_name(&target, name))
+                    .and_then(|name| {
+                        let output = target.join(name);
+                        write_private_copy(&input, &output, &profile)?;
+                        Ok(output)
+                    });
+                match outcome {
+                    Ok(output) => PrivateExportResult {
+                        source,
+                        target: Some(output.to_string_lossy().into_owned()),
+                        error: None,
+                    },
+                    Err(e) => {
+                        log::warn!("private export failed for {}: {}", source, e);
+                        PrivateExportResult {
+                            source,
+                            target: None,
+                            error: Some(e),
+                        }
+                    }
+                }
+            })
+            .collect();
+        log::info!(
+            "export_private_copies target={} files={} failed={}",
+            target.display(),
+            results.len(),
+            results.iter().filter(|r| r.error.is_some()).count()
+        );
+        Ok(results)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn find_media_with_location(album_path: String) -> Result<Vec<LocatedMedia>, String> {
+    let dir = PathBuf::from(&album_path);
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", album_path));
+    }
+    async_runtime::spawn_blocking(move || {
+        let files: Vec<PathBuf> = fs::read_dir(&dir)
+            .map_err(|e| e.to_string())?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_file())
+            .filter(|p| has_extension(p, IMAGE_EXTENSIONS) || has_extension(p, VIDEO_EXTENSIONS))
+            .collect();
+        let mut located: Vec<LocatedMedia> =
+            files.par_iter().filter_map(|p| locate_media(p)).collect();
+        located.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(located)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,289 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime;
+
+use crate::{
+    album::move_media_artifacts,
+    constants::IMAGE_EXTENSIONS,
+    library::ensure_online,
+    metadata::{
+        exif_camera_model, get_metadata_with_favorite, rename_media_entries_caller_holds_lock,
+        unpack_file_meta, DetachedFileMeta,
+    },
+    preload::enqueue_preload,
+    settings::read_settings,
+    template::{render_file_name, validate_template, TemplateContext},
+    timeline::shot_date,
+    util::{has_extension, STORE_WRITE_LOCK},
+};
+
+const DEFAULT_START_INDEX: usize = 1;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameOptions {
+    pub template: String,
+    #[serde(default)]
+    pub start_index: Option<usize>,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub keep_extension_case: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenameStatus {
+    Renamed,
+    Unchanged,
+    Collision,
+    Failed,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameEntry {
+    pub name: String,
+    pub new_name: Option<String>,
+    pub status: RenameStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameBatchResult {
+    pub applied: bool,
+    pub entries: Vec<RenameEntry>,
+}
+
+impl RenameEntry {
+    fn failed(name: &str, error: String) -> Self {
+        Self {
+            name: name.to_string(),
+            new_name: None,
+            status: RenameStatus::Failed,
+            error: Some(error),
+        }
+    }
+
+    fn collide(&mut self, error: String) {
+        self.status = RenameStatus::Collision;
+        self.error = Some(error);
+    }
+}
+
+fn render_new_name(
+    album: &Path,
+    album_name: &str,
+    name: &str,
+    index: usize,
+    options: &RenameOptions,
+) -> Result<String, String> {
+    if Path::new(name).file_name().and_then(|n| n.to_str()) != Some(name) {
+        return Err("Invalid file name".to_string());
+    }
+    let path = album.join(name);
+    if !path.is_file() {
+        return Err("File not found".to_string());
+    }
+    let meta: Option<DetachedFileMeta> = get_metadata_with_favorite(&path)
+        .ok()
+        .and_then(|m| unpack_file_meta(&m.meta).ok());
+    let camera = if has_extension(&path, IMAGE_EXTENSIONS) {
+        exif_camera_model(&path)
+    } else {
+        None
+    };
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid file name")?;
+    let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+    let ext = if options.keep_extension_case {
+        ext.to_string()
+    } else {
+        ext.to_ascii_lowercase()
+    };
+    let ctx = TemplateContext {
+        name: stem,
+        album: album_name,
+        index,
+        taken: meta.and_then(|m| m.s.or(m.a)).and_then(shot_date),
+        width: meta.and_then(|m| m.w),
+        height: meta.and_then(|m| m.h),
+        camera: camera.as_deref(),
+    };
+    render_file_name(&options.template, &ctx, &ext)
+}
+
+fn plan_renames(album: &Path, names: &[String], options: &RenameOptions) -> Vec<RenameEntry> {
+    let album_name = album
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let start = options.start_index.unwrap_or(DEFAULT_START_INDEX);
+    let mut entries: Vec<RenameEntry> = names
+        .iter()
+        .enumerate()
+        .map(
+            |(i, name)| match render_new_name(album, album_name, name, start + i, options) {
+                Ok(new_name) => RenameEntry {
+                    name: name.clone(),
+                    status: if &new_name == name {
+                        RenameStatus::Unchanged
+                    } else {
+                        RenameStatus::Renamed
+                    },
+                    new_name: Some(new_name),
+                    error: None,
+                },
+                Err(e) => RenameEntry::failed(name, e),
+            },
+        )
+        .collect();
+
+    let mut targets: HashMap<String, usize> = HashMap::new();
+    for entry in &entries {
+        if let Some(new_name) = &entry.new_name {
+            *targets.entry(new_name.to_lowercase()).or_default() += 1;
+        }
+    }
+    let sources: HashSet<String> = entries
+        .iter()
+        .filter(|e| e.status != RenameStatus::Failed)
+        .map(|e| e.name.to_lowercase())
+        .collect();
+    for entry in &mut entries {
+        let Some(new_name) = entry.new_name.clone() else {
+            continue;
+        };
+        let key = new_name.to_lowercase();
+        if targets.get(&key).copied().unwrap_or(0) > 1 {
+            entry.collide(format!(
+                "\"{}\" is produced for more than one file",
+                new_name
+            ));
+        } else if album.join(&new_name).exists() && !sources.contains(&key) {
+            entry.collide(format!("\"{}\" already exists", new_name));
+        }
+    }
+    entries
+}
+
+fn staging_name(index: usize) -> String {
+    format!(".room237-rename-{}-{}.tmp", std::process::id(), index)
+}
+
+fn apply_renames(
+    album: &Path,
+    renames: &[(String, String)],
+    move_artifacts: bool,
+) -> Result<Vec<(String, String)>, String> {
+    let _guard = STORE_WRITE_LOCK.lock().unwrap();
+    let mut staged: Vec<(String, &str, &str)> = Vec::with_capacity(renames.len());
+    for (i, (from, to)) in renames.iter().enumerate() {
+        let temp = staging_name(i);
+        if let Err(e) = fs::rename(album.join(from), album.join(&temp)) {
+            for (temp, from, _) in staged.iter().rev() {
+                let _ = fs::rename(album.join(temp), album.join(from));
+                if move_artifacts {
+                    move_media_artifacts(album, temp, album, from);
+                }
+            }
+            return Err(format!("Failed to rename {}: {}", from, e));
+        }
+        if move_artifacts {
+            move_media_artifacts(album, from, album, &temp);
+        }
+        staged.push((temp, from, to));
+    }
+
+    let mut failed = Vec::new();
+    let mut done: Vec<(String, String)> = Vec::with_capacity(staged.len());
+    for (temp, from, to) in staged {
+        let (target, name) = match fs::rename(album.join(&temp), album.join(to)) {
+            Ok(()) => {
+                done.push((from.to_string(), to.to_string()));
+                (to, to)
+            }
+            Err(e) => {
+                log::warn!("rename_media_batch: {} → {} failed: {}", from, to, e);
+                let _ = fs::rename(album.join(&temp), album.join(from));
+                failed.push((from.to_string(), e.to_string()));
+                (from, from)
+            }
+        };
+        if move_artifacts {
+            move_media_artifacts(album, &temp, album, target);
+        }
+        log::info!("rename {} → {}", from, name);
+    }
+
+    if move_artifacts {
+        rename_media_entries_caller_holds_lock(album, &done)?;
+    } else {
+        log::info!("Skipped renaming thumbnails/metadata due to settings");
+    }
+    Ok(failed)
+}
+
+fn rename_media_batch_blocking(
+    album: PathBuf,
+    names: Vec<String>,
+    options: RenameOptions,
+) -> Result<RenameBatchResult, String> {
+    let mut entries = plan_renames(&album, &names, &options);
+    let blocked = entries
+        .iter()
+        .any(|e| matches!(e.status, RenameStatus::Collision | RenameStatus::Failed));
+    if options.dry_run || blocked {
+        return Ok(RenameBatchResult {
+            applied: false,
+            entries,
+        });
+    }
+
+    let renames: Vec<(String, String)> = entries
+        .iter()
+        .filter(|e| e.status == RenameStatus::Renamed)
+        .filter_map(|e| Some((e.name.clone(), e.new_name.clone()?)))
+        .collect();
+    let move_artifacts = read_settings().album.move_rename_thumbs_and_meta;
+    let failed = apply_renames(&album, &renames, move_artifacts)?;
+    for (name, error) in failed {
+        if let Some(entry) = entries.iter_mut().find(|e| e.name == name) {
+            entry.status = RenameStatus::Failed;
+            entry.error = Some(error);
+        }
+    }
+    if !move_artifacts {
+        enqueue_preload(&album);
+    }
+    Ok(RenameBatchResult {
+        applied: true,
+        entries,
+    })
+}
+
+#[tauri::command]
+pub async fn rename_media_batch(
+    album_path: String,
+    names: Vec<String>,
+    options: RenameOptions,
+) -> Result<RenameBatchResult, String> {
+    let album = PathBuf::from(&album_path);
+    ensure_online(&album)?;
+    if !album.is_dir() {
+        return Err(format!("{} is not a directory", album_path));
+    }
+    validate_template(&options.template)?;
+    async_runtime::spawn_blocking(move || rename_media_batch_blocking(album, names, options))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,229 @@
+use std::{
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::{
+    preload::scheduler_changed,
+    settings::{read_settings, BatteryPolicy, SchedulerPolicy},
+};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+const LOAD_PAUSE_MIN_HOLD: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulerMode {
+    Run,
+    Throttle,
+    Pause,
+}
+
+impl SchedulerMode {
+    fn as_u8(self) -> u8 {
+        match self {
+            SchedulerMode::Run => 0,
+            SchedulerMode::Throttle => 1,
+            SchedulerMode::Pause => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => SchedulerMode::Throttle,
+            2 => SchedulerMode::Pause,
+            _ => SchedulerMode::Run,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SchedulerStatus {
+    mode: SchedulerMode,
+    reasons: Vec<&'static str>,
+    load_per_core: Option<f32>,
+    on_battery: Option<bool>,
+    user_active: bool,
+}
+
+impl Default for SchedulerStatus {
+    fn default() -> Self {
+        Self {
+            mode: SchedulerMode::Run,
+            reasons: Vec::new(),
+            load_per_core: None,
+            on_battery: None,
+            user_active: false,
+        }
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+static STATUS: Lazy<Mutex<SchedulerStatus>> = Lazy::new(|| Mutex::new(SchedulerStatus::default()));
+static LAST_USER_ACTIVITY: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+static LOAD_PAUSED_SINCE: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+pub(crate) fn scheduler_mode() -> SchedulerMode {
+    SchedulerMode::from_u8(MODE.load(Ordering::SeqCst))
+}
+
+pub(crate) fn scheduler_status() -> SchedulerStatus {
+    STATUS.lock().unwrap().clone()
+}
+
+fn load_per_core() -> Option<f32> {
+    #[cfg(target_family = "unix")]
+    {
+        let mut loads = [0f64; 1];
+        let n = unsafe { libc::getloadavg(loads.as_mut_ptr(), 1) };
+        if n < 1 {
+            return None;
+        }
+        let cores = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Some((loads[0] / cores as f64) as f32)
+    }
+    #[cfg(not(target_family = "unix"))]
+    {
+        None
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+fn on_battery() -> Option<bool> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let entries = fs::read_dir("/sys/class/power_supply").ok()?;
+    let mut has_battery = false;
+    let mut discharging = false;
+    for dir in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        match read_trimmed(&dir.join("type")).as_deref() {
+            Some("Mains") | Some("USB")
+                if read_trimmed(&dir.join("online")).as_deref() == Some("1") =>
+            {
+                return Some(false);
+            }
+            Some("Battery") => {
+                has_battery = true;
+                if read_trimmed(&dir.join("status")).as_deref() == Some("Discharging") {
+                    discharging = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    has_battery.then_some(discharging)
+}
+
+fn evaluate(policy: &SchedulerPolicy) -> SchedulerStatus {
+    let user_active = LAST_USER_ACTIVITY
+        .lock()
+        .unwrap()
+        .map(|t| t.elapsed() < Duration::from_secs(policy.user_idle_secs))
+        .unwrap_or(false);
+    let mut status = SchedulerStatus {
+        user_active,
+        ..SchedulerStatus::default()
+    };
+    if !policy.enabled {
+        return status;
+    }
+    status.load_per_core = load_per_core();
+    status.on_battery = on_battery();
+
+    let mut pause = Vec::new();
+    let mut throttle = Vec::new();
+    if let Some(load) = status.load_per_core {
+        // Our own workers feed the load average, so a load pause only lifts once
+        // the average has had time to settle and dropped below the throttle level.
+        let mut paused_since = LOAD_PAUSED_SINCE.lock().unwrap();
+        let holding = paused_since
+            .map(|since| {
+                since.elapsed() < LOAD_PAUSE_MIN_HOLD || load >= policy.load_throttle_per_core
+            })
+            .unwrap_or(false);
+        if load >= policy.load_pause_per_core || holding {
+            paused_since.get_or_insert_with(Instant::now);
+            pause.push("load");
+        } else {
+            *paused_since = None;
+            if load >= policy.load_throttle_per_core {
+                throttle.push("load");
+            }
+        }
+    }
+    if status.on_battery == Some(true) {
+        match policy.on_battery {
+            BatteryPolicy::Ignore => {}
+            BatteryPolicy::Throttle => throttle.push("battery"),
+            BatteryPolicy::Pause => pause.push("battery"),
+        }
+    }
+    if user_active && policy.throttle_while_active {
+        throttle.push("user-active");
+    }
+
+    if !pause.is_empty() {
+        status.mode = SchedulerMode::Pause;
+        status.reasons = pause;
+    } else if !throttle.is_empty() {
+        status.mode = SchedulerMode::Throttle;
+        status.reasons = throttle;
+    }
+    status
+}
+
+pub(crate) fn refresh_scheduler() {
+    let next = evaluate(&read_settings().preload.scheduler);
+    let changed = {
+        let mut status = STATUS.lock().unwrap();
+        let changed = status.mode != next.mode || status.reasons != next.reasons;
+        *status = next.clone();
+        changed
+    };
+    if !changed {
+        return;
+    }
+    MODE.store(next.mode.as_u8(), Ordering::SeqCst);
+    log::info!("preload scheduler: {:?} {:?}", next.mode, next.reasons);
+    scheduler_changed();
+}
+
+pub(crate) fn ensure_scheduler_monitor() {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    refresh_scheduler();
+    thread::spawn(|| loop {
+        thread::sleep(SAMPLE_INTERVAL);
+        refresh_scheduler();
+    });
+}
+
+pub(crate) fn throttle_if_needed() {
+    if scheduler_mode() == SchedulerMode::Throttle {
+        let delay = read_settings().preload.scheduler.throttle_delay_ms;
+        thread::sleep(Duration::from_millis(delay));
+    }
+}
+
+#[tauri::command]
+pub fn report_user_activity() {
+    *LAST_USER_ACTIVITY.lock().unwrap() = Some(Instant::now());
+    refresh_scheduler();
+}
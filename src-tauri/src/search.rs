@@ -0,0 +1,634 @@
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+};
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime;
+
+use crate::{
+    album::{walk_album_entries, AlbumDirEntry, LibraryMediaEntry},
+    catalog::catalog_snapshot,
+    metadata::{read_album_meta, unpack_file_meta, DetachedFileMeta, FileMetaEntry},
+    tags::normalize_tag,
+    thumb::{ensure_thumb, thumb_path},
+    util::glob_match,
+};
+
+const DEFAULT_PAGE_SIZE: usize = 200;
+const MAX_PAGE_SIZE: usize = 1000;
+
+pub(crate) struct IndexedMedia {
+    pub(crate) album: usize,
+    pub(crate) name: String,
+    pub(crate) entry: FileMetaEntry,
+    pub(crate) meta: DetachedFileMeta,
+}
+
+impl IndexedMedia {
+    pub(crate) fn timestamp(&self) -> Option<u64> {
+        self.meta.s.or(self.meta.a)
+    }
+}
+
+pub(crate) struct LibrarySnapshot {
+    pub(crate) albums: Vec<AlbumDirEntry>,
+    pub(crate) media: Vec<IndexedMedia>,
+    pub(crate) thumbs: Option<PathBuf>,
+}
+
+impl LibrarySnapshot {
+    pub(crate) fn to_entry(&self, item: &IndexedMedia) -> LibraryMediaEntry {
+        let album = &self.albums[item.album];
+        LibraryMediaEntry {
+            meta: item.entry.meta.clone().unwrap_or_default(),
+            name: item.name.clone(),
+            album_path: album.path.to_string_lossy().to_string(),
+            album_name: album.relative_path.clone(),
+            album_id: album.relative_path.clone(),
+            favorite: if item.entry.favorite {
+                Some(true)
+            } else {
+                None
+            },
+            tags: item.entry.tags.clone(),
+            thumb: self.thumbs.as_ref().and_then(|thumbs| {
+                let thumb = thumbs
+                    .join(&album.relative_path)
+                    .join(format!("{}.webp", item.name));
+                thumb
+                    .is_file()
+                    .then(|| thumb.to_string_lossy().into_owned())
+            }),
+        }
+    }
+}
+
+pub(crate) fn representative_thumbnail(
+    snapshot: &LibrarySnapshot,
+    items: &[&IndexedMedia],
+    generate: bool,
+) -> Option<String> {
+    let thumb_dir_of =
+        |item: &IndexedMedia| snapshot.albums[item.album].path.join(".room237-thumb");
+    let existing = items.iter().find_map(|item| {
+        let original = snapshot.albums[item.album].path.join(&item.name);
+        thumb_path(&original, &thumb_dir_of(item))
+            .ok()
+            .filter(|t| t.exists())
+    });
+    if let Some(thumb) = existing {
+        return Some(thumb.to_string_lossy().into_owned());
+    }
+    if !generate {
+        return None;
+    }
+    let first = items.first()?;
+    let original = snapshot.albums[first.album].path.join(&first.name);
+    ensure_thumb(&original, &thumb_dir_of(first))
+        .ok()
+        .map(|t| t.to_string_lossy().into_owned())
+}
+
+pub(crate) fn scan_library(root: &Path) -> Result<LibrarySnapshot, String> {
+    let albums = walk_album_entries(root)?;
+    let media: Vec<IndexedMedia> = albums
+        .par_iter()
+        .enumerate()
+        .flat_map_iter(|(idx, album)| {
+            let album_meta = read_album_meta(&album.path);
+            album_meta
+                .files
+                .into_iter()
+                .filter_map(|(name, entry)| {
+                    let meta = unpack_file_meta(entry.meta.as_deref()?).ok()?;
+                    if !album.path.join(&name).is_file() {
+                        return None;
+                    }
+                    Some(IndexedMedia {
+                        album: idx,
+                        name,
+                        entry,
+                        meta,
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    Ok(LibrarySnapshot {
+        albums,
+        media,
+        thumbs: None,
+    })
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchSortKey {
+    #[default]
+    Shot,
+    Added,
+    Name,
+    Album,
+    Width,
+    Height,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSort {
+    #[serde(default)]
+    pub key: SearchSortKey,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPage {
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub items: Vec<LibraryMediaEntry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum NumField {
+    Width,
+    Height,
+    Shot,
+    Added,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Predicate {
+    Image,
+    Video,
+    Favorite(bool),
+    Range {
+        field: NumField,
+        lo: Option<u64>,
+        hi: Option<u64>,
+    },
+    Album(String),
+    Name(String),
+    Tag(String),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Filter {
+    All,
+    Pred(Predicate),
+    Not(Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Word(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            c if in_quotes => current.push(c),
+            '(' | ')' => {
+                if !current.is_empty() || quoted {
+                    tokens.push(Token::Word(std::mem::take(&mut current)));
+                    quoted = false;
+                }
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() || quoted {
+                    tokens.push(Token::Word(std::mem::take(&mut current)));
+                    quoted = false;
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("Unterminated quote in search query".to_string());
+    }
+    if !current.is_empty() || quoted {
+        tokens.push(Token::Word(current));
+    }
+    Ok(tokens)
+}
+
+fn is_keyword(token: &Token, keyword: &str) -> bool {
+    matches!(token, Token::Word(w) if w.eq_ignore_ascii_case(keyword))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Filter, String> {
+        let mut left = self.parse_and()?;
+        while self.peek().map(|t| is_keyword(t, "or")).unwrap_or(false) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = Filter::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Filter, String> {
+        let mut left = self.parse_not()?;
+        loop {
+            match self.peek() {
+                None | Some(Token::Close) => break,
+                Some(t) if is_keyword(t, "or") => break,
+                Some(t) if is_keyword(t, "and") => {
+                    self.pos += 1;
+                }
+                _ => {}
+            }
+            let right = self.parse_not()?;
+            left = Filter::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Filter, String> {
+        match self.peek() {
+            Some(t) if is_keyword(t, "not") => {
+                self.pos += 1;
+                Ok(Filter::Not(Box::new(self.parse_not()?)))
+            }
+            Some(Token::Word(w)) if w.len() > 1 && w.starts_with('-') => {
+                let rest = w[1..].to_string();
+                self.tokens[self.pos] = Token::Word(rest);
+                Ok(Filter::Not(Box::new(self.parse_primary()?)))
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Filter, String> {
+        match self.next() {
+            Some(Token::Open) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("Missing closing parenthesis in search query".to_string()),
+                }
+            }
+            Some(Token::Word(w)) => parse_term(&w),
+            Some(Token::Close) => Err("Unexpected ')' in search query".to_string()),
+            None => Err("Unexpected end of search query".to_string()),
+        }
+    }
+}
+
+pub(crate) fn parse_filter(query: &str) -> Result<Filter, String> {
+    let tokens = tokenize(query)?;
+    if tokens.is_empty() {
+        return Ok(Filter::All);
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let filter = parser.parse_or()?;
+    if parser.pos < parser.tokens.len() {
+        return Err("Unexpected ')' in search query".to_string());
+    }
+    Ok(filter)
+}
+
+fn split_term(term: &str) -> Option<(&str, &str, &str)> {
+    let ops = [">=", "<=", "!=", ">", "<", "=", ":"];
+    let (idx, op) = ops
+        .iter()
+        .filter_map(|op| term.find(op).map(|i| (i, *op)))
+        .min_by(|a, b| a.0.cmp(&b.0).then(b.1.len().cmp(&a.1.len())))?;
+    if idx == 0 {
+        return None;
+    }
+    Some((&term[..idx], op, &term[idx + op.len()..]))
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "" | "true" | "yes" | "1" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        other => Err(format!("Invalid boolean \"{other}\" in search query")),
+    }
+}
+
+fn date_bounds(value: &str) -> Result<(u64, u64), String> {
+    let parts: Vec<&str> = value.split(['-', '/', '.']).collect();
+    let num = |s: &str| {
+        s.parse::<u32>()
+            .map_err(|_| format!("Invalid date \"{value}\" in search query"))
+    };
+    let year = |s: &str| {
+        s.parse::<i32>()
+            .map_err(|_| format!("Invalid date \"{value}\" in search query"))
+    };
+    let (start, end) = match parts.as_slice() {
+        [y] => {
+            let y = year(y)?;
+            (
+                NaiveDate::from_ymd_opt(y, 1, 1),
+                y.checked_add(1)
+                    .and_then(|next| NaiveDate::from_ymd_opt(next, 1, 1)),
+            )
+        }
+        [y, m] => {
+            let (y, m) = (year(y)?, num(m)?);
+            let next = if m == 12 {
+                y.checked_add(1).map(|next| (next, 1))
+            } else {
+                Some((y, m + 1))
+            };
+            (
+                NaiveDate::from_ymd_opt(y, m, 1),
+                next.and_then(|(y, m)| NaiveDate::from_ymd_opt(y, m, 1)),
+            )
+        }
+        [y, m, d] => {
+            let start = NaiveDate::from_ymd_opt(year(y)?, num(m)?, num(d)?);
+            (start, start.and_then(|s| s.succ_opt()))
+        }
+        _ => (None, None),
+    };
+    let to_ts = |d: NaiveDate| {
+        Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).unwrap())
+            .timestamp()
+            .max(0) as u64
+    };
+    match (start, end) {
+        (Some(s), Some(e)) => Ok((to_ts(s), to_ts(e))),
+        _ => Err(format!("Invalid date \"{value}\" in search query")),
+    }
+}
+
+fn value_bounds(field: NumField, value: &str) -> Result<(u64, u64), String> {
+    match field {
+        NumField::Shot | NumField::Added => date_bounds(value),
+        NumField::Width | NumField::Height => {
+            let n = value
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid number \"{value}\" in search query"))?;
+            Ok((n, n.saturating_add(1)))
+        }
+    }
+}
+
+fn range_filter(field: NumField, op: &str, value: &str) -> Result<Filter, String> {
+    if let Some((from, to)) = value.split_once("..") {
+        let lo = if from.is_empty() {
+            None
+        } else {
+            Some(value_bounds(field, from)?.0)
+        };
+        let hi = if to.is_empty() {
+            None
+        } else {
+            Some(value_bounds(field, to)?.1)
+        };
+        return Ok(Filter::Pred(Predicate::Range { field, lo, hi }));
+    }
+    let (start, end) = value_bounds(field, value)?;
+    let (lo, hi) = match op {
+        ">" => (Some(end), None),
+        ">=" => (Some(start), None),
+        "<" => (None, Some(start)),
+        "<=" => (None, Some(end)),
+        _ => (Some(start), Some(end)),
+    };
+    let pred = Filter::Pred(Predicate::Range { field, lo, hi });
+    if op == "!=" {
+        Ok(Filter::Not(Box::new(pred)))
+    } else {
+        Ok(pred)
+    }
+}
+
+fn text_filter(pred: Predicate, op: &str) -> Result<Filter, String> {
+    match op {
+        ":" | "=" => Ok(Filter::Pred(pred)),
+        "!=" => Ok(Filter::Not(Box::new(Filter::Pred(pred)))),
+        other => Err(format!("Operator \"{other}\" is not supported here")),
+    }
+}
+
+fn parse_term(term: &str) -> Result<Filter, String> {
+    let Some((field, op, value)) = split_term(term) else {
+        return Ok(match term.to_ascii_lowercase().as_str() {
+            "favorite" | "favorites" | "fav" => Filter::Pred(Predicate::Favorite(true)),
+            "image" | "images" | "photo" | "photos" => Filter::Pred(Predicate::Image),
+            "video" | "videos" => Filter::Pred(Predicate::Video),
+            _ => Filter::Pred(Predicate::Name(term.to_string())),
+        });
+    };
+
+    match field.to_ascii_lowercase().as_str() {
+        "kind" | "type" | "is" => {
+            let pred = match value.to_ascii_lowercase().as_str() {
+                "image" | "photo" => Predicate::Image,
+                "video" => Predicate::Video,
+                "favorite" | "fav" => Predicate::Favorite(true),
+                other => return Err(format!("Unknown media kind \"{other}\"")),
+            };
+            text_filter(pred, op)
+        }
+        "favorite" | "fav" => text_filter(Predicate::Favorite(parse_bool(value)?), op),
+        "width" | "w" => range_filter(NumField::Width, op, value),
+        "height" | "h" => range_filter(NumField::Height, op, value),
+        "shot" | "taken" | "date" => range_filter(NumField::Shot, op, value),
+        "added" => range_filter(NumField::Added, op, value),
+        "album" | "in" => text_filter(Predicate::Album(value.to_string()), op),
+        "name" | "file" => text_filter(Predicate::Name(value.to_string()), op),
+        "tag" => {
+            let tag = normalize_tag(value).ok_or("Tag cannot be empty")?;
+            text_filter(Predicate::Tag(tag), op)
+        }
+        other => Err(format!("Unknown search field \"{other}\"")),
+    }
+}
+
+fn has_wildcard(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+fn matches_album(pattern: &str, relative_path: &str) -> bool {
+    if has_wildcard(pattern) {
+        return glob_match(pattern, relative_path);
+    }
+    let pattern = pattern.trim_matches('/').to_lowercase();
+    let relative_path = relative_path.to_lowercase();
+    relative_path == pattern || relative_path.starts_with(&format!("{pattern}/"))
+}
+
+fn matches_name(pattern: &str, name: &str) -> bool {
+    if has_wildcard(pattern) {
+        return glob_match(pattern, name);
+    }
+    name.to_lowercase().contains(&pattern.to_lowercase())
+}
+
+impl Filter {
+    pub(crate) fn matches(&self, snapshot: &LibrarySnapshot, item: &IndexedMedia) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Not(inner) => !inner.matches(snapshot, item),
+            Filter::And(a, b) => a.matches(snapshot, item) && b.matches(snapshot, item),
+            Filter::Or(a, b) => a.matches(snapshot, item) || b.matches(snapshot, item),
+            Filter::Pred(pred) => match pred {
+                Predicate::Image => item.meta.i,
+                Predicate::Video => item.meta.v,
+                Predicate::Favorite(f) => item.entry.favorite == *f,
+                Predicate::Range { field, lo, hi } => {
+                    let value = match field {
+                        NumField::Width => item.meta.w.map(u64::from),
+                        NumField::Height => item.meta.h.map(u64::from),
+                        NumField::Shot => item.meta.s,
+                        NumField::Added => item.meta.a,
+                    };
+                    match value {
+                        Some(v) => {
+                            lo.map(|l| v >= l).unwrap_or(true) && hi.map(|h| v < h).unwrap_or(true)
+                        }
+                        None => false,
+                    }
+                }
+                Predicate::Album(pattern) => {
+                    matches_album(pattern, &snapshot.albums[item.album].relative_path)
+                }
+                Predicate::Name(pattern) => matches_name(pattern, &item.name),
+                Predicate::Tag(tag) => item.entry.tags.contains(tag),
+            },
+        }
+    }
+}
+
+pub(crate) fn sort_media(
+    snapshot: &LibrarySnapshot,
+    items: &mut [&IndexedMedia],
+    sort: SearchSort,
+) {
+    let album_of = |m: &IndexedMedia| snapshot.albums[m.album].relative_path.as_str();
+    items.sort_by(|a, b| {
+        let primary = match sort.key {
+            SearchSortKey::Shot => a.timestamp().cmp(&b.timestamp()),
+            SearchSortKey::Added => a.meta.a.cmp(&b.meta.a),
+            SearchSortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SearchSortKey::Album => album_of(a).cmp(album_of(b)),
+            SearchSortKey::Width => a.meta.w.cmp(&b.meta.w),
+            SearchSortKey::Height => a.meta.h.cmp(&b.meta.h),
+        };
+        let primary = match sort.direction {
+            SortDirection::Asc => primary,
+            SortDirection::Desc => primary.reverse(),
+        };
+        if primary != Ordering::Equal {
+            return primary;
+        }
+        album_of(a).cmp(album_of(b)).then(a.name.cmp(&b.name))
+    });
+}
+
+pub(crate) fn run_query<'a>(
+    snapshot: &'a LibrarySnapshot,
+    filter: &Filter,
+    sort: SearchSort,
+) -> Vec<&'a IndexedMedia> {
+    let mut matched: Vec<&IndexedMedia> = snapshot
+        .media
+        .par_iter()
+        .filter(|item| filter.matches(snapshot, item))
+        .collect();
+    sort_media(snapshot, &mut matched, sort);
+    matched
+}
+
+#[tauri::command]
+pub async fn search_media(
+    root_dir: String,
+    query: String,
+    sort: Option<SearchSort>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<SearchPage, String> {
+    async_runtime::spawn_blocking(move || {
+        let t_start = std::time::Instant::now();
+        let root = PathBuf::from(&root_dir);
+        let snapshot = if root.is_dir() {
+            None
+        } else {
+            Some(
+                catalog_snapshot(&root)
+                    .ok_or_else(|| format!("{} is not a directory", root.display()))?,
+            )
+        };
+        let filter = parse_filter(&query)?;
+        let sort = sort.unwrap_or_default();
+        let page = page.unwrap_or(0);
+        let page_size = page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let snapshot = match snapshot {
+            Some(snapshot) => snapshot,
+            None => scan_library(&root)?,
+        };
+        let matched = run_query(&snapshot, &filter, sort);
+        let items = matched
+            .iter()
+            .skip(page.saturating_mul(page_size))
+            .take(page_size)
+            .map(|item| snapshot.to_entry(item))
+            .collect();
+
+        log::info!(
+            "search_media \"{}\" matched={} scanned={} elapsed={:?}",
+            query,
+            matched.len(),
+            snapshot.media.len(),
+            t_start.elapsed()
+        );
+        Ok(SearchPage {
+            total: matched.len(),
+            page,
+            page_size,
+            items,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -1,6 +1,6 @@
 use std::{
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
 };
 
@@ -11,6 +11,8 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tauri::{path::BaseDirectory, AppHandle, Manager, Wry};
 
+use crate::{library::library_overrides, preload::apply_settings_change};
+
 const SETTINGS_FILE: &str = "settings.json";
 const HASH_SCHEMA_VERSION: &str = "1";
 
@@ -187,7 +189,6 @@ pub struct ThumbnailSettings {
     pub image_webp_quality: u8,
     pub image_webp_compression_level: u8,
     pub video_seek_seconds: f32,
-    pub lock_poll_ms: u64,
 }
 
 impl Default for ThumbnailSettings {
@@ -197,7 +198,6 @@ impl Default for ThumbnailSettings {
             image_webp_quality: 75,
             image_webp_compression_level: 3,
             video_seek_seconds: 1.0,
-            lock_poll_ms: 50,
         }
     }
 }
@@ -209,7 +209,6 @@ impl ThumbnailSettings {
             image_webp_quality: self.image_webp_quality.clamp(30, 95),
             image_webp_compression_level: self.image_webp_compression_level.clamp(0, 9),
             video_seek_seconds: self.video_seek_seconds.clamp(0.0, 30.0),
-            lock_poll_ms: self.lock_poll_ms.clamp(5, 250),
         }
     }
 }
@@ -259,6 +258,58 @@ pub struct PreloadSettings {
     pub thumb_hash_queue_delay_ms: u64,
     pub thumb_hash_only_after_idle: bool,
     pub thumb_hash_retry_on_thumb_change: bool,
+    #[serde(default)]
+    pub scheduler: SchedulerPolicy,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BatteryPolicy {
+    Ignore,
+    #[default]
+    Throttle,
+    Pause,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SchedulerPolicy {
+    pub enabled: bool,
+    pub load_throttle_per_core: f32,
+    pub load_pause_per_core: f32,
+    pub on_battery: BatteryPolicy,
+    pub throttle_while_active: bool,
+    pub user_idle_secs: u64,
+    pub throttle_delay_ms: u64,
+}
+
+impl Default for SchedulerPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            load_throttle_per_core: 0.8,
+            load_pause_per_core: 1.5,
+            on_battery: BatteryPolicy::default(),
+            throttle_while_active: true,
+            user_idle_secs: 30,
+            throttle_delay_ms: 250,
+        }
+    }
+}
+
+impl SchedulerPolicy {
+    fn clamp(self) -> Self {
+        let load_throttle_per_core = self.load_throttle_per_core.clamp(0.1, 8.0);
+        Self {
+            enabled: self.enabled,
+            load_throttle_per_core,
+            load_pause_per_core: self.load_pause_per_core.clamp(load_throttle_per_core, 16.0),
+            on_battery: self.on_battery,
+            throttle_while_active: self.throttle_while_active,
+            user_idle_secs: self.user_idle_secs.clamp(5, 600),
+            throttle_delay_ms: self.throttle_delay_ms.clamp(10, 5000),
+        }
+    }
 }
 
 impl Default for PreloadSettings {
@@ -271,6 +322,7 @@ impl Default for PreloadSettings {
             thumb_hash_queue_delay_ms: 10,
             thumb_hash_only_after_idle: true,
             thumb_hash_retry_on_thumb_change: true,
+            scheduler: SchedulerPolicy::default(),
         }
     }
 }
@@ -285,6 +337,7 @@ impl PreloadSettings {
             thumb_hash_queue_delay_ms: self.thumb_hash_queue_delay_ms.clamp(0, 100),
             thumb_hash_only_after_idle: self.thumb_hash_only_after_idle,
             thumb_hash_retry_on_thumb_change: self.thumb_hash_retry_on_thumb_change,
+            scheduler: self.scheduler.clamp(),
         }
     }
 }
@@ -318,11 +371,23 @@ impl MetadataSettings {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CollisionPolicy {
+    #[default]
+    Suffix,
+    Skip,
+    OverwriteIfIdentical,
+    Ask,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AlbumSettings {
     pub rename_cleanup_delay_secs: u64,
     pub move_rename_thumbs_and_meta: bool,
+    #[serde(default)]
+    pub collision_policy: CollisionPolicy,
 }
 
 impl Default for AlbumSettings {
@@ -330,6 +395,7 @@ impl Default for AlbumSettings {
         Self {
             rename_cleanup_delay_secs: 1,
             move_rename_thumbs_and_meta: true,
+            collision_policy: CollisionPolicy::default(),
         }
     }
 }
@@ -339,6 +405,7 @@ impl AlbumSettings {
         Self {
             rename_cleanup_delay_secs: self.rename_cleanup_delay_secs.clamp(0, 10),
             move_rename_thumbs_and_meta: self.move_rename_thumbs_and_meta,
+            collision_policy: self.collision_policy,
         }
     }
 }
@@ -349,6 +416,8 @@ pub struct PrivacySettings {
     pub enabled: bool,
     pub lockscreen_enabled: bool,
     pub confirm_open_enabled: bool,
+    #[serde(default)]
+    pub export_profile: PrivacyExportProfile,
 }
 
 impl Default for PrivacySettings {
@@ -357,6 +426,7 @@ impl Default for PrivacySettings {
             enabled: false,
             lockscreen_enabled: false,
             confirm_open_enabled: false,
+            export_profile: PrivacyExportProfile::default(),
         }
     }
 }
@@ -367,6 +437,29 @@ impl PrivacySettings {
             enabled: self.enabled,
             lockscreen_enabled: self.lockscreen_enabled,
             confirm_open_enabled: self.confirm_open_enabled,
+            export_profile: self.export_profile,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrivacyExportProfile {
+    pub strip_location: bool,
+    pub strip_serial_numbers: bool,
+    pub strip_owner: bool,
+    pub strip_xmp: bool,
+    pub strip_all: bool,
+}
+
+impl Default for PrivacyExportProfile {
+    fn default() -> Self {
+        Self {
+            strip_location: true,
+            strip_serial_numbers: true,
+            strip_owner: true,
+            strip_xmp: true,
+            strip_all: false,
         }
     }
 }
@@ -418,6 +511,17 @@ impl AdvancedSettings {
         }
     }
 
+    pub fn with_overrides(&self, overrides: &SettingsOverrides) -> Self {
+        let mut settings = self.clone();
+        if let Some(duplicates) = &overrides.duplicates {
+            settings.duplicates = duplicates.clone();
+        }
+        if let Some(thumbnails) = &overrides.thumbnails {
+            settings.thumbnails = thumbnails.clone();
+        }
+        settings.clamp()
+    }
+
     pub fn thumb_version(&self) -> String {
         let mut hasher = Sha256::new();
         hasher.update(format!(
@@ -470,6 +574,24 @@ impl AdvancedSettings {
     }
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsOverrides {
+    #[serde(default)]
+    pub duplicates: Option<DuplicatesSettings>,
+    #[serde(default)]
+    pub thumbnails: Option<ThumbnailSettings>,
+}
+
+impl SettingsOverrides {
+    pub fn clamp(self) -> Self {
+        Self {
+            duplicates: self.duplicates.map(DuplicatesSettings::clamp),
+            thumbnails: self.thumbnails.map(ThumbnailSettings::clamp),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HashConfig {
     pub size: (u32, u32),
@@ -548,6 +670,14 @@ pub fn read_settings() -> AdvancedSettings {
         .unwrap_or_default()
 }
 
+pub fn read_settings_for(path: &Path) -> AdvancedSettings {
+    let settings = read_settings();
+    match library_overrides(path) {
+        Some(overrides) => settings.with_overrides(&overrides),
+        None => settings,
+    }
+}
+
 #[tauri::command]
 pub fn get_settings(state: tauri::State<SettingsState>) -> Result<AdvancedSettings, String> {
     Ok(state.get())
@@ -558,10 +688,16 @@ pub fn update_settings(
     state: tauri::State<SettingsState>,
     settings: AdvancedSettings,
 ) -> Result<AdvancedSettings, String> {
-    state.update(settings)
+    let previous = state.get();
+    let updated = state.update(settings)?;
+    apply_settings_change(&previous, &updated);
+    Ok(updated)
 }
 
 #[tauri::command]
 pub fn reset_settings(state: tauri::State<SettingsState>) -> Result<AdvancedSettings, String> {
-    state.reset()
+    let previous = state.get();
+    let updated = state.reset()?;
+    apply_settings_change(&previous, &updated);
+    Ok(updated)
 }
@@ -0,0 +1,290 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::async_runtime;
+
+use crate::{
+    album::LibraryMediaEntry,
+    metadata::{root_meta_file, AlbumProperties, DetachedAlbum},
+    search::{
+        parse_filter, representative_thumbnail, run_query, scan_library, IndexedMedia,
+        LibrarySnapshot, SearchSort,
+    },
+};
+
+pub(crate) const SMART_ALBUMS_FILE: &str = "smart_albums.json";
+pub(crate) const SMART_ALBUM_PREFIX: &str = "smart:";
+
+static SMART_ALBUMS_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartAlbum {
+    pub id: String,
+    pub name: String,
+    pub query: String,
+    #[serde(default)]
+    pub sort: SearchSort,
+    #[serde(default)]
+    pub created: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct SmartAlbumStore {
+    #[serde(default)]
+    albums: Vec<SmartAlbum>,
+}
+
+fn store_path(root: &Path) -> PathBuf {
+    root_meta_file(root, SMART_ALBUMS_FILE)
+}
+
+fn read_store(root: &Path) -> SmartAlbumStore {
+    fs::read_to_string(store_path(root))
+        .ok()
+        .and_then(|txt| serde_json::from_str::<SmartAlbumStore>(&txt).ok())
+        .unwrap_or_default()
+}
+
+fn write_store(root: &Path, store: &SmartAlbumStore) -> Result<(), String> {
+    let path = store_path(root);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn ensure_root(root_dir: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(root_dir);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    Ok(root)
+}
+
+fn validated_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Album name cannot be empty".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+fn ensure_unique_name(
+    store: &SmartAlbumStore,
+    name: &str,
+    except: Option<&str>,
+) -> Result<(), String> {
+    if store
+        .albums
+        .iter()
+        .any(|a| Some(a.id.as_str()) != except && a.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("Smart album \"{}\" already exists", name));
+    }
+    Ok(())
+}
+
+fn new_smart_album_id(name: &str) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{name}|{nanos}"));
+    hex::encode(hasher.finalize())[0..12].to_string()
+}
+
+pub(crate) fn smart_album_path(id: &str) -> String {
+    format!("{SMART_ALBUM_PREFIX}{id}")
+}
+
+pub(crate) fn parse_smart_album_id(album_id: &str) -> Option<&str> {
+    album_id
+        .strip_prefix(SMART_ALBUM_PREFIX)
+        .filter(|id| !id.is_empty())
+}
+
+pub(crate) fn load_smart_albums(root: &Path) -> Vec<SmartAlbum> {
+    read_store(root).albums
+}
+
+fn resolve_smart_album<'a>(
+    snapshot: &'a LibrarySnapshot,
+    album: &SmartAlbum,
+) -> Result<Vec<&'a IndexedMedia>, String> {
+    let filter = parse_filter(&album.query)?;
+    Ok(run_query(snapshot, &filter, album.sort))
+}
+
+pub(crate) fn detached_smart_albums(root: &Path) -> Vec<DetachedAlbum> {
+    let albums = load_smart_albums(root);
+    if albums.is_empty() {
+        return Vec::new();
+    }
+    let snapshot = match scan_library(root) {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("failed to scan library for smart albums: {}", e);
+            return Vec::new();
+        }
+    };
+
+    albums
+        .iter()
+        .filter_map(|album| {
+            let matched = match resolve_smart_album(&snapshot, album) {
+                Ok(m) => m,
+                Err(e) => {
+                    log::warn!("smart album {} has invalid query: {}", album.id, e);
+                    return None;
+                }
+            };
+            let thumb = representative_thumbnail(&snapshot, &matched, true);
+            Some(DetachedAlbum {
+                path: smart_album_path(&album.id),
+                name: album.name.clone(),
+                size: matched.len(),
+                thumb_path: thumb,
+                relative_path: smart_album_path(&album.id),
+                parent: None,
+                smart: Some(true),
+                properties: AlbumProperties::default(),
+                offline: None,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn rename_smart_album(root: &Path, id: &str, name: &str) -> Result<SmartAlbum, String> {
+    let name = validated_name(name)?;
+    let _guard = SMART_ALBUMS_LOCK.lock().unwrap();
+    let mut store = read_store(root);
+    ensure_unique_name(&store, &name, Some(id))?;
+    let album = store
+        .albums
+        .iter_mut()
+        .find(|a| a.id == id)
+        .ok_or("Smart album not found")?;
+    album.name = name;
+    let updated = album.clone();
+    write_store(root, &store)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn list_smart_albums(root_dir: String) -> Result<Vec<SmartAlbum>, String> {
+    let root = ensure_root(&root_dir)?;
+    Ok(load_smart_albums(&root))
+}
+
+#[tauri::command]
+pub fn create_smart_album(
+    root_dir: String,
+    name: String,
+    query: String,
+    sort: Option<SearchSort>,
+) -> Result<SmartAlbum, String> {
+    let root = ensure_root(&root_dir)?;
+    let name = validated_name(&name)?;
+    parse_filter(&query)?;
+
+    let _guard = SMART_ALBUMS_LOCK.lock().unwrap();
+    let mut store = read_store(&root);
+    ensure_unique_name(&store, &name, None)?;
+    let album = SmartAlbum {
+        id: new_smart_album_id(&name),
+        name,
+        query,
+        sort: sort.unwrap_or_default(),
+        created: chrono::Utc::now().timestamp().max(0) as u64,
+    };
+    store.albums.push(album.clone());
+    write_store(&root, &store)?;
+    log::info!("created smart album {} ({})", album.name, album.id);
+    Ok(album)
+}
+
+#[tauri::command]
+pub fn update_smart_album(
+    root_dir: String,
+    id: String,
+    name: Option<String>,
+    query: Option<String>,
+    sort: Option<SearchSort>,
+) -> Result<SmartAlbum, String> {
+    let root = ensure_root(&root_dir)?;
+    let id = parse_smart_album_id(&id).unwrap_or(&id).to_string();
+    if let Some(q) = query.as_ref() {
+        parse_filter(q)?;
+    }
+    let name = name.map(|n| validated_name(&n)).transpose()?;
+
+    let _guard = SMART_ALBUMS_LOCK.lock().unwrap();
+    let mut store = read_store(&root);
+    if let Some(name) = name.as_ref() {
+        ensure_unique_name(&store, name, Some(&id))?;
+    }
+    let album = store
+        .albums
+        .iter_mut()
+        .find(|a| a.id == id)
+        .ok_or("Smart album not found")?;
+    if let Some(name) = name {
+        album.name = name;
+    }
+    if let Some(query) = query {
+        album.query = query;
+    }
+    if let Some(sort) = sort {
+        album.sort = sort;
+    }
+    let updated = album.clone();
+    write_store(&root, &store)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn delete_smart_album(root_dir: String, id: String) -> Result<(), String> {
+    let root = ensure_root(&root_dir)?;
+    let id = parse_smart_album_id(&id).unwrap_or(&id).to_string();
+
+    let _guard = SMART_ALBUMS_LOCK.lock().unwrap();
+    let mut store = read_store(&root);
+    let before = store.albums.len();
+    store.albums.retain(|a| a.id != id);
+    if store.albums.len() == before {
+        return Err("Smart album not found".to_string());
+    }
+    write_store(&root, &store)
+}
+
+#[tauri::command]
+pub async fn get_smart_album_media(
+    root_dir: String,
+    id: String,
+) -> Result<Vec<LibraryMediaEntry>, String> {
+    async_runtime::spawn_blocking(move || {
+        let root = ensure_root(&root_dir)?;
+        let id = parse_smart_album_id(&id).unwrap_or(&id).to_string();
+        let album = load_smart_albums(&root)
+            .into_iter()
+            .find(|a| a.id == id)
+            .ok_or("Smart album not found")?;
+        let snapshot = scan_library(&root)?;
+        let matched = resolve_smart_album(&snapshot, &album)?;
+        Ok(matched
+            .into_iter()
+            .map(|item| snapshot.to_entry(item))
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,213 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use serde::Serialize;
+
+use crate::{
+    album::{library_media_entry, walk_album_entries, LibraryMediaEntry},
+    index::AlbumIndex,
+    library::ensure_online,
+    metadata::{
+        get_file_metadata, read_album_meta, update_album_meta_and, DetachedMediaEntry,
+        FileMetaEntry,
+    },
+};
+
+const MAX_TAG_LEN: usize = 64;
+
+static TAG_INDEX: AlbumIndex<Vec<String>> = AlbumIndex {
+    file: "tags.json",
+    lock: Mutex::new(()),
+    extract: indexed_tags,
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+pub(crate) fn normalize_tag(raw: &str) -> Option<String> {
+    let collapsed = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    Some(collapsed.to_lowercase().chars().take(MAX_TAG_LEN).collect())
+}
+
+fn normalize_tags(raw: &[String]) -> Vec<String> {
+    raw.iter()
+        .filter_map(|t| normalize_tag(t))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn indexed_tags(entry: &FileMetaEntry) -> Option<Vec<String>> {
+    (!entry.tags.is_empty()).then(|| entry.tags.clone())
+}
+
+fn apply_tag_change(
+    root_dir: String,
+    album_path: String,
+    names: Vec<String>,
+    tags: Vec<String>,
+    add: bool,
+) -> Result<Vec<DetachedMediaEntry>, String> {
+    let root = PathBuf::from(&root_dir);
+    ensure_online(&root)?;
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    let dir = PathBuf::from(&album_path);
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", album_path));
+    }
+    if !dir.starts_with(&root) {
+        return Err("Album path escapes root".to_string());
+    }
+    let tags = normalize_tags(&tags);
+    if tags.is_empty() {
+        return Err("No valid tags provided".to_string());
+    }
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cached = read_album_meta(&dir);
+    for name in &names {
+        let file_path = dir.join(name);
+        if !file_path.is_file() {
+            return Err(format!("{} does not exist", file_path.display()));
+        }
+        if cached
+            .files
+            .get(name)
+            .and_then(|e| e.meta.as_ref())
+            .is_none()
+        {
+            get_file_metadata(&file_path.to_string_lossy())?;
+        }
+    }
+
+    let album = update_album_meta_and(
+        &dir,
+        |album| {
+            for name in &names {
+                let entry = album.files.entry(name.clone()).or_default();
+                let mut current: BTreeSet<String> = entry.tags.drain(..).collect();
+                for tag in &tags {
+                    if add {
+                        current.insert(tag.clone());
+                    } else {
+                        current.remove(tag);
+                    }
+                }
+                entry.tags = current.into_iter().collect();
+            }
+            Ok(())
+        },
+        |album| {
+            if let Err(e) = TAG_INDEX.record_album(&root, &dir, album) {
+                log::warn!("failed to update tag index for {}: {}", dir.display(), e);
+            }
+        },
+    )?;
+
+    Ok(names
+        .iter()
+        .filter_map(|name| {
+            let entry = album.files.get(name)?;
+            Some(DetachedMediaEntry {
+                meta: entry.meta.clone()?,
+                name: name.clone(),
+                favorite: if entry.favorite { Some(true) } else { None },
+                tags: entry.tags.clone(),
+                thumb: None,
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn add_tags(
+    root_dir: String,
+    album_path: String,
+    names: Vec<String>,
+    tags: Vec<String>,
+) -> Result<Vec<DetachedMediaEntry>, String> {
+    apply_tag_change(root_dir, album_path, names, tags, true)
+}
+
+#[tauri::command]
+pub fn remove_tags(
+    root_dir: String,
+    album_path: String,
+    names: Vec<String>,
+    tags: Vec<String>,
+) -> Result<Vec<DetachedMediaEntry>, String> {
+    apply_tag_change(root_dir, album_path, names, tags, false)
+}
+
+#[tauri::command]
+pub fn list_tags(root_dir: String) -> Result<Vec<TagCount>, String> {
+    let root = PathBuf::from(&root_dir);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+
+    let index = TAG_INDEX.load_synced(&root)?;
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for album in index.albums.values() {
+        for tags in album.files.values() {
+            for tag in tags {
+                *counts.entry(tag.clone()).or_default() += 1;
+            }
+        }
+    }
+
+    Ok(counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect())
+}
+
+#[tauri::command]
+pub fn list_media_by_tag(root_dir: String, tag: String) -> Result<Vec<LibraryMediaEntry>, String> {
+    let root = PathBuf::from(&root_dir);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    let tag = normalize_tag(&tag).ok_or("Tag cannot be empty")?;
+
+    let index = TAG_INDEX.load_synced(&root)?;
+    let tagged_albums: HashSet<&str> = index
+        .albums
+        .iter()
+        .filter(|(_, a)| a.files.values().any(|tags| tags.contains(&tag)))
+        .map(|(rel, _)| rel.as_str())
+        .collect();
+
+    let mut results = Vec::new();
+    for album in walk_album_entries(&root)? {
+        if !tagged_albums.contains(album.relative_path.as_str()) {
+            continue;
+        }
+        let album_meta = read_album_meta(&album.path);
+        for (name, entry) in album_meta.files.iter() {
+            if !entry.tags.contains(&tag) || !album.path.join(name).exists() {
+                continue;
+            }
+            if let Some(media) = library_media_entry(&album, name, entry) {
+                results.push(media);
+            }
+        }
+    }
+
+    results.sort_by(|a, b| a.album_name.cmp(&b.album_name).then(a.name.cmp(&b.name)));
+    Ok(results)
+}
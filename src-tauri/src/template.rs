@@ -0,0 +1,148 @@
+use chrono::{DateTime, Datelike, Timelike, Utc};
+
+pub(crate) struct TemplateContext<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) album: &'a str,
+    pub(crate) index: usize,
+    pub(crate) taken: Option<DateTime<Utc>>,
+    pub(crate) width: Option<u32>,
+    pub(crate) height: Option<u32>,
+    pub(crate) camera: Option<&'a str>,
+}
+
+impl TemplateContext<'_> {
+    fn sample() -> TemplateContext<'static> {
+        TemplateContext {
+            name: "sample",
+            album: "album",
+            index: 1,
+            taken: Some(DateTime::<Utc>::UNIX_EPOCH),
+            width: Some(1),
+            height: Some(1),
+            camera: Some("camera"),
+        }
+    }
+}
+
+fn render_token(token: &str, ctx: &TemplateContext) -> Result<String, String> {
+    let (key, arg) = match token.split_once(':') {
+        Some((k, a)) => (k, Some(a)),
+        None => (token, None),
+    };
+    let date = |f: fn(&DateTime<Utc>) -> String| {
+        ctx.taken
+            .as_ref()
+            .map(f)
+            .ok_or_else(|| format!("{{{key}}} requires a capture date"))
+    };
+    let value = match key {
+        "name" => ctx.name.to_string(),
+        "album" => ctx.album.to_string(),
+        "index" => {
+            let width: usize = match arg {
+                Some(a) => a
+                    .parse()
+                    .ok()
+                    .filter(|w| *w <= 12)
+                    .ok_or_else(|| format!("Invalid index padding \"{a}\""))?,
+                None => 0,
+            };
+            return Ok(format!("{:0width$}", ctx.index, width = width));
+        }
+        "year" => date(|d| format!("{:04}", d.year()))?,
+        "month" => date(|d| format!("{:02}", d.month()))?,
+        "day" => date(|d| format!("{:02}", d.day()))?,
+        "hour" => date(|d| format!("{:02}", d.hour()))?,
+        "minute" => date(|d| format!("{:02}", d.minute()))?,
+        "second" => date(|d| format!("{:02}", d.second()))?,
+        "date" => date(|d| d.format("%Y-%m-%d").to_string())?,
+        "time" => date(|d| d.format("%H%M%S").to_string())?,
+        "width" => ctx.width.map(|w| w.to_string()).unwrap_or_default(),
+        "height" => ctx.height.map(|h| h.to_string()).unwrap_or_default(),
+        "camera" => ctx.camera.unwrap_or_default().to_string(),
+        _ => return Err(format!("Unknown template field {{{token}}}")),
+    };
+    if arg.is_some() {
+        return Err(format!("{{{key}}} does not take an argument"));
+    }
+    Ok(value)
+}
+
+pub(crate) fn render_template(template: &str, ctx: &TemplateContext) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => token.push(ch),
+                        None => return Err("Unclosed \"{\" in template".to_string()),
+                    }
+                }
+                out.push_str(&render_token(token.trim(), ctx)?);
+            }
+            '}' => return Err("Unmatched \"}\" in template".to_string()),
+            _ => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+pub(crate) fn validate_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("Template cannot be empty".to_string());
+    }
+    render_template(template, &TemplateContext::sample()).map(|_| ())
+}
+
+pub(crate) fn sanitize_component(raw: &str) -> String {
+    let cleaned: String = raw
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    cleaned.trim().trim_matches('.').trim().to_string()
+}
+
+pub(crate) fn render_file_name(
+    template: &str,
+    ctx: &TemplateContext,
+    ext: &str,
+) -> Result<String, String> {
+    let stem = sanitize_component(&render_template(template, ctx)?);
+    if stem.is_empty() {
+        return Err("Template produced an empty file name".to_string());
+    }
+    Ok(if ext.is_empty() {
+        stem
+    } else {
+        format!("{stem}.{ext}")
+    })
+}
+
+pub(crate) fn render_relative_dir(template: &str, ctx: &TemplateContext) -> Result<String, String> {
+    let rendered = render_template(template, ctx)?;
+    let segments: Vec<String> = rendered
+        .split(['/', '\\'])
+        .map(sanitize_component)
+        .filter(|s| !s.is_empty())
+        .collect();
+    if segments.is_empty() {
+        return Err("Template produced an empty path".to_string());
+    }
+    Ok(segments.join("/"))
+}
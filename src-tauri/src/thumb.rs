@@ -1,19 +1,34 @@
 use std::{
+    collections::HashSet,
     fs,
     fs::OpenOptions,
-    io,
+    io::{self, Write},
     path::{Path, PathBuf},
-    thread,
-    time::Duration,
+    sync::{Condvar, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use crate::{
     constants::{IMAGE_EXTENSIONS, VIDEO_EXTENSIONS},
     metadata::{load_thumb_version, write_thumb_version},
-    settings::{read_settings, AdvancedSettings},
-    util::{apply_ffmpeg_tuning, ffmpeg_timeout, newer_than, wait_with_timeout},
+    settings::{read_settings_for, AdvancedSettings},
+    util::{apply_ffmpeg_tuning, ffmpeg_timeout, newer_than, process_alive, wait_with_timeout},
 };
 use ffmpeg_sidecar::command::FfmpegCommand;
+use once_cell::sync::Lazy;
+
+static THUMBS_IN_FLIGHT: Lazy<(Mutex<HashSet<PathBuf>>, Condvar)> =
+    Lazy::new(|| (Mutex::new(HashSet::new()), Condvar::new()));
+
+struct InFlightThumb(PathBuf);
+
+impl Drop for InFlightThumb {
+    fn drop(&mut self) {
+        let (lock, cv) = &*THUMBS_IN_FLIGHT;
+        lock.lock().unwrap().remove(&self.0);
+        cv.notify_all();
+    }
+}
 
 pub fn thumb_path(original: &Path, thumb_dir: &Path) -> Result<PathBuf, String> {
     Ok(thumb_dir.join(format!(
@@ -29,6 +44,88 @@ fn thumb_lock_path(thumb: &Path) -> PathBuf {
     thumb.with_extension("lock")
 }
 
+fn thumb_lock_timeout() -> Duration {
+    (ffmpeg_timeout() * 2).max(Duration::from_secs(60))
+}
+
+fn read_thumb_lock_owner(lock_file: &Path) -> Option<(u32, u64)> {
+    let content = fs::read_to_string(lock_file).ok()?;
+    let mut parts = content.split_whitespace();
+    let pid = parts.next()?.parse().ok()?;
+    let created = parts.next()?.parse().ok()?;
+    Some((pid, created))
+}
+
+fn thumb_lock_is_stale(lock_file: &Path) -> bool {
+    let now = SystemTime::now();
+    let created = match read_thumb_lock_owner(lock_file) {
+        Some((pid, _)) if !process_alive(pid) => return true,
+        Some((_, created)) => UNIX_EPOCH + Duration::from_secs(created),
+        None => match lock_file.metadata().and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(_) => return false,
+        },
+    };
+    now.duration_since(created)
+        .map(|age| age >= thumb_lock_timeout())
+        .unwrap_or(false)
+}
+
+fn acquire_thumb_lock(lock_file: &Path, thumb: &Path) -> Result<(), String> {
+    for _ in 0..2 {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(lock_file)
+        {
+            Ok(mut f) => {
+                let created = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                let _ = write!(f, "{} {}", std::process::id(), created);
+                return Ok(());
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                let own = read_thumb_lock_owner(lock_file)
+                    .map(|(pid, _)| pid == std::process::id())
+                    .unwrap_or(false);
+                if !own && !thumb_lock_is_stale(lock_file) {
+                    break;
+                }
+                log::warn!("removing stale thumb lock {}", lock_file.display());
+                let _ = fs::remove_file(lock_file);
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    Err(format!(
+        "{} is being generated by another process",
+        thumb.display()
+    ))
+}
+
+pub fn sweep_stale_thumb_locks(thumb_dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(thumb_dir) else {
+        return 0;
+    };
+    let in_flight = THUMBS_IN_FLIGHT.0.lock().unwrap().clone();
+    let mut removed = 0;
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        if path.extension().and_then(|e| e.to_str()) != Some("lock") {
+            continue;
+        }
+        if in_flight.contains(&path.with_extension("webp")) || !thumb_lock_is_stale(&path) {
+            continue;
+        }
+        log::info!("removing stale thumb lock {}", path.display());
+        if fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
 fn generate_image_thumbnail(
     input: &Path,
     output: &Path,
@@ -125,7 +222,7 @@ fn generate_video_thumbnail(
 }
 
 pub fn ensure_thumb(path: &Path, thumb_dir: &Path) -> Result<PathBuf, String> {
-    let settings = read_settings();
+    let settings = read_settings_for(path);
     ensure_thumb_with_settings(path, thumb_dir, &settings)
 }
 
@@ -136,12 +233,30 @@ pub fn ensure_thumb_with_settings(
 ) -> Result<PathBuf, String> {
     let thumb = thumb_path(path, thumb_dir)?;
     let thumb_version = settings.thumb_version();
-    let existing_version = load_thumb_version(path);
-    let thumb_fresh = thumb.exists() && newer_than(&thumb, path).unwrap_or(false);
-    if thumb_fresh && existing_version.as_deref() == Some(&thumb_version) {
+    let is_current = |thumb: &Path| {
+        thumb.exists()
+            && newer_than(thumb, path).unwrap_or(false)
+            && load_thumb_version(path).as_deref() == Some(&thumb_version)
+    };
+    if is_current(&thumb) {
         return Ok(thumb);
     }
 
+    let _in_flight = {
+        let (lock, cv) = &*THUMBS_IN_FLIGHT;
+        let mut in_flight = lock.lock().unwrap();
+        let mut waited = false;
+        while in_flight.contains(&thumb) {
+            waited = true;
+            in_flight = cv.wait(in_flight).unwrap();
+        }
+        if waited && is_current(&thumb) {
+            return Ok(thumb);
+        }
+        in_flight.insert(thumb.clone());
+        InFlightThumb(thumb.clone())
+    };
+
     log::info!("generating thumb {}→{}", path.display(), thumb.display());
     if let Some(parent) = thumb_dir.parent() {
         let _ = fs::create_dir_all(parent);
@@ -149,28 +264,7 @@ pub fn ensure_thumb_with_settings(
     let _ = fs::create_dir_all(thumb_dir);
 
     let lock_file = thumb_lock_path(&thumb);
-    loop {
-        match OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&lock_file)
-        {
-            Ok(f) => {
-                drop(f);
-                break;
-            }
-            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
-                if thumb.exists()
-                    && newer_than(&thumb, path).unwrap_or(false)
-                    && existing_version.as_deref() == Some(&thumb_version)
-                {
-                    return Ok(thumb);
-                }
-                thread::sleep(Duration::from_millis(settings.thumbnails.lock_poll_ms));
-            }
-            Err(e) => return Err(e.to_string()),
-        }
-    }
+    acquire_thumb_lock(&lock_file, &thumb)?;
 
     let res = match path
         .extension()
@@ -0,0 +1,203 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::Serialize;
+use tauri::async_runtime;
+
+use crate::{
+    album::LibraryMediaEntry,
+    search::{
+        representative_thumbnail, scan_library, sort_media, IndexedMedia, LibrarySnapshot,
+        SearchSort, SearchSortKey, SortDirection,
+    },
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineDay {
+    pub day: u32,
+    pub count: usize,
+    pub thumb_path: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineMonth {
+    pub month: u32,
+    pub count: usize,
+    pub thumb_path: Option<String>,
+    pub days: Vec<TimelineDay>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineYear {
+    pub year: i32,
+    pub count: usize,
+    pub thumb_path: Option<String>,
+    pub months: Vec<TimelineMonth>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timeline {
+    pub years: Vec<TimelineYear>,
+    pub undated: usize,
+}
+
+type DayKey = (i32, u32, u32);
+
+pub(crate) fn shot_date(ts: u64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(ts as i64, 0).single()
+}
+
+fn day_key(item: &IndexedMedia) -> Option<DayKey> {
+    let date = shot_date(item.meta.s?)?;
+    Some((date.year(), date.month(), date.day()))
+}
+
+fn favorites_first<'a>(items: &[&'a IndexedMedia]) -> Vec<&'a IndexedMedia> {
+    let mut ordered: Vec<&IndexedMedia> = items.to_vec();
+    ordered.sort_by_key(|item| !item.entry.favorite);
+    ordered
+}
+
+fn bucket_thumbnail(snapshot: &LibrarySnapshot, items: &[&IndexedMedia]) -> Option<String> {
+    representative_thumbnail(snapshot, &favorites_first(items), false)
+}
+
+fn ensure_root(root_dir: &str) -> Result<PathBuf, String> {
+    let root = PathBuf::from(root_dir);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    Ok(root)
+}
+
+fn build_timeline(snapshot: &LibrarySnapshot) -> Timeline {
+    let mut by_day: BTreeMap<DayKey, Vec<&IndexedMedia>> = BTreeMap::new();
+    let mut undated = 0usize;
+    for item in &snapshot.media {
+        match day_key(item) {
+            Some(key) => by_day.entry(key).or_default().push(item),
+            None => undated += 1,
+        }
+    }
+
+    let mut years: Vec<TimelineYear> = Vec::new();
+    let mut month_items: Vec<&IndexedMedia> = Vec::new();
+    let mut year_items: Vec<&IndexedMedia> = Vec::new();
+
+    for ((year, month, day), items) in by_day.iter().rev() {
+        if years.last().map(|y| y.year) != Some(*year) {
+            if let Some(last) = years.last_mut() {
+                if let Some(last_month) = last.months.last_mut() {
+                    last_month.thumb_path = bucket_thumbnail(snapshot, &month_items);
+                }
+                last.thumb_path = bucket_thumbnail(snapshot, &year_items);
+            }
+            month_items.clear();
+            year_items.clear();
+            years.push(TimelineYear {
+                year: *year,
+                count: 0,
+                thumb_path: None,
+                months: Vec::new(),
+            });
+        }
+        let current_year = years.last_mut().unwrap();
+        if current_year.months.last().map(|m| m.month) != Some(*month) {
+            if let Some(last) = current_year.months.last_mut() {
+                last.thumb_path = bucket_thumbnail(snapshot, &month_items);
+            }
+            month_items.clear();
+            current_year.months.push(TimelineMonth {
+                month: *month,
+                count: 0,
+                thumb_path: None,
+                days: Vec::new(),
+            });
+        }
+        let current_month = current_year.months.last_mut().unwrap();
+        current_month.days.push(TimelineDay {
+            day: *day,
+            count: items.len(),
+            thumb_path: bucket_thumbnail(snapshot, items),
+        });
+        current_month.count += items.len();
+        current_year.count += items.len();
+        month_items.extend(items.iter().copied());
+        year_items.extend(items.iter().copied());
+    }
+
+    if let Some(last_year) = years.last_mut() {
+        if let Some(last_month) = last_year.months.last_mut() {
+            last_month.thumb_path = bucket_thumbnail(snapshot, &month_items);
+        }
+        last_year.thumb_path = bucket_thumbnail(snapshot, &year_items);
+    }
+
+    Timeline { years, undated }
+}
+
+#[tauri::command]
+pub async fn get_timeline(root_dir: String) -> Result<Timeline, String> {
+    async_runtime::spawn_blocking(move || {
+        let t_start = std::time::Instant::now();
+        let root = ensure_root(&root_dir)?;
+        let snapshot = scan_library(&root)?;
+        let timeline = build_timeline(&snapshot);
+        log::info!(
+            "get_timeline {} years={} undated={} elapsed={:?}",
+            root.display(),
+            timeline.years.len(),
+            timeline.undated,
+            t_start.elapsed()
+        );
+        Ok(timeline)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn get_timeline_range(
+    root_dir: String,
+    year: i32,
+    month: Option<u32>,
+    day: Option<u32>,
+) -> Result<Vec<LibraryMediaEntry>, String> {
+    if day.is_some() && month.is_none() {
+        return Err("A day bucket requires a month".to_string());
+    }
+    async_runtime::spawn_blocking(move || {
+        let root = ensure_root(&root_dir)?;
+        let snapshot = scan_library(&root)?;
+        let mut matched: Vec<&IndexedMedia> = snapshot
+            .media
+            .iter()
+            .filter(|item| match day_key(item) {
+                Some((y, m, d)) => {
+                    y == year
+                        && month.map(|mm| mm == m).unwrap_or(true)
+                        && day.map(|dd| dd == d).unwrap_or(true)
+                }
+                None => false,
+            })
+            .collect();
+        sort_media(
+            &snapshot,
+            &mut matched,
+            SearchSort {
+                key: SearchSortKey::Shot,
+                direction: SortDirection::Asc,
+            },
+        );
+        Ok(matched
+            .into_iter()
+            .map(|item| snapshot.to_entry(item))
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
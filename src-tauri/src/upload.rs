@@ -0,0 +1,322 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{
+    async_runtime,
+    ipc::{InvokeBody, Request},
+    AppHandle, Emitter, Wry,
+};
+
+use crate::{
+    album::{add_media_files_blocking, next_available_name, IncomingFile},
+    library::ensure_online,
+    metadata::DetachedMediaEntry,
+};
+
+const UPLOAD_DIR: &str = ".room237-upload";
+const PART_EXT: &str = "part";
+const UPLOAD_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+const UPLOAD_ID_HEADER: &str = "upload-id";
+const UPLOAD_OFFSET_HEADER: &str = "upload-offset";
+const FINALIZE_ATTEMPTS: usize = 16;
+
+static UPLOADS: Lazy<Mutex<HashMap<String, UploadSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_UPLOAD: AtomicU64 = AtomicU64::new(1);
+static FINALIZE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+struct UploadSession {
+    album: PathBuf,
+    name: String,
+    temp: PathBuf,
+    file: Option<File>,
+    expected_size: Option<u64>,
+    received: u64,
+    last_activity: Instant,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadState {
+    Uploading,
+    Finished,
+    Aborted,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgress {
+    pub upload_id: String,
+    pub album_path: String,
+    pub name: String,
+    pub received: u64,
+    pub total: Option<u64>,
+    pub state: UploadState,
+}
+
+fn upload_dir(album: &Path) -> PathBuf {
+    album.join(UPLOAD_DIR)
+}
+
+fn emit_upload_progress(
+    app: &AppHandle<Wry>,
+    id: &str,
+    session: &UploadSession,
+    state: UploadState,
+) {
+    let _ = app.emit(
+        "upload-progress",
+        UploadProgress {
+            upload_id: id.to_string(),
+            album_path: session.album.to_string_lossy().into_owned(),
+            name: session.name.clone(),
+            received: session.received,
+            total: session.expected_size,
+            state,
+        },
+    );
+}
+
+pub(crate) fn sweep_stale_uploads(album: &Path) {
+    let mut active: Vec<PathBuf> = Vec::new();
+    {
+        let mut uploads = UPLOADS.lock().unwrap();
+        uploads.retain(|id, session| {
+            if session.last_activity.elapsed() < UPLOAD_IDLE_TIMEOUT {
+                active.push(session.temp.clone());
+                return true;
+            }
+            log::warn!("dropping abandoned upload {} ({})", id, session.name);
+            let _ = fs::remove_file(&session.temp);
+            false
+        });
+    }
+
+    let dir = upload_dir(album);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+    let now = SystemTime::now();
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        if active.contains(&path) {
+            continue;
+        }
+        let stale = path
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| now.duration_since(t).ok())
+            .map(|age| age >= UPLOAD_IDLE_TIMEOUT)
+            .unwrap_or(true);
+        if stale {
+            log::info!("removing stale partial upload {}", path.display());
+            let _ = fs::remove_file(&path);
+        }
+    }
+    let _ = fs::remove_dir(&dir);
+}
+
+fn finalize_upload(temp: &Path, album: &Path, name: &str) -> Result<String, String> {
+    for _ in 0..FINALIZE_ATTEMPTS {
+        let final_name = next_available_name(album, name)?;
+        match fs::hard_link(temp, album.join(&final_name)) {
+            Ok(()) => {
+                let _ = fs::remove_file(temp);
+                return Ok(final_name);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                log::debug!("hard link unavailable in {}: {}", album.display(), e);
+                // No hard links on this filesystem (e.g. exFAT); serialise instead.
+                let _guard = FINALIZE_LOCK.lock().unwrap();
+                let final_name = next_available_name(album, name)?;
+                fs::rename(temp, album.join(&final_name)).map_err(|e| e.to_string())?;
+                return Ok(final_name);
+            }
+        }
+    }
+    Err(format!("Could not find a free name for \"{}\"", name))
+}
+
+fn header<'a>(request: &'a Request<'_>, name: &str) -> Result<&'a str, String> {
+    request
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| format!("Missing {} header", name))
+}
+
+#[tauri::command]
+pub fn begin_upload(album_path: String, name: String, size: Option<u64>) -> Result<String, String> {
+    let album = PathBuf::from(&album_path);
+    ensure_online(&album)?;
+    if !album.is_dir() {
+        return Err(format!("{} is not a directory", album_path));
+    }
+    let name = name.trim().to_string();
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return Err(format!("Invalid file name \"{}\"", name));
+    }
+    sweep_stale_uploads(&album);
+
+    let id = format!(
+        "{}-{}",
+        std::process::id(),
+        NEXT_UPLOAD.fetch_add(1, Ordering::SeqCst)
+    );
+    let dir = upload_dir(&album);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let temp = dir.join(format!("{id}.{PART_EXT}"));
+    let file = File::create(&temp).map_err(|e| e.to_string())?;
+    log::info!("begin upload {} {} into {}", id, name, album.display());
+    UPLOADS.lock().unwrap().insert(
+        id.clone(),
+        UploadSession {
+            album,
+            name,
+            temp,
+            file: Some(file),
+            expected_size: size,
+            received: 0,
+            last_activity: Instant::now(),
+        },
+    );
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn append_upload_chunk(app: AppHandle<Wry>, request: Request<'_>) -> Result<u64, String> {
+    let id = header(&request, UPLOAD_ID_HEADER)?.to_string();
+    let offset: u64 = header(&request, UPLOAD_OFFSET_HEADER)?
+        .parse()
+        .map_err(|_| "Invalid upload offset".to_string())?;
+    let InvokeBody::Raw(chunk) = request.body() else {
+        return Err("Upload chunks must be sent as raw bytes".to_string());
+    };
+    let chunk = chunk.clone();
+
+    // Take the file out of the session so the write happens without holding the
+    // global lock; a concurrent chunk for the same upload sees it as busy.
+    let mut file = {
+        let mut uploads = UPLOADS.lock().unwrap();
+        let session = uploads
+            .get_mut(&id)
+            .ok_or_else(|| format!("Upload {} not found", id))?;
+        if offset != session.received {
+            return Err(format!(
+                "Unexpected offset {} for upload {}, expected {}",
+                offset, id, session.received
+            ));
+        }
+        let new_size = session.received + chunk.len() as u64;
+        if session.expected_size.map(|s| new_size > s).unwrap_or(false) {
+            return Err(format!("Upload {} exceeds its declared size", id));
+        }
+        session
+            .file
+            .take()
+            .ok_or_else(|| format!("Upload {} is busy or closed", id))?
+    };
+
+    let (file, written) = async_runtime::spawn_blocking(move || {
+        let written = file.write_all(&chunk).map(|_| chunk.len() as u64);
+        (file, written)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut uploads = UPLOADS.lock().unwrap();
+    let Some(session) = uploads.get_mut(&id) else {
+        return Err(format!("Upload {} was aborted", id));
+    };
+    session.file = Some(file);
+    session.last_activity = Instant::now();
+    let written = written.map_err(|e| format!("Failed to write upload {}: {}", id, e))?;
+    session.received += written;
+    emit_upload_progress(&app, &id, session, UploadState::Uploading);
+    Ok(session.received)
+}
+
+#[tauri::command]
+pub async fn finish_upload(
+    app: AppHandle<Wry>,
+    upload_id: String,
+) -> Result<DetachedMediaEntry, String> {
+    let mut session = {
+        let mut uploads = UPLOADS.lock().unwrap();
+        let session = uploads
+            .get(&upload_id)
+            .ok_or_else(|| format!("Upload {} not found", upload_id))?;
+        if session.file.is_none() {
+            return Err(format!("Upload {} still has a chunk in flight", upload_id));
+        }
+        if let Some(expected) = session.expected_size {
+            if expected != session.received {
+                return Err(format!(
+                    "Upload {} is incomplete: {} of {} bytes",
+                    upload_id, session.received, expected
+                ));
+            }
+        }
+        uploads.remove(&upload_id).unwrap()
+    };
+    async_runtime::spawn_blocking(move || {
+        let result = (|| {
+            if let Some(file) = session.file.take() {
+                file.sync_all().map_err(|e| e.to_string())?;
+            }
+            let final_name = finalize_upload(&session.temp, &session.album, &session.name)?;
+            let dest = session.album.join(&final_name);
+            let _ = fs::remove_dir(upload_dir(&session.album));
+            log::info!("finished upload {} → {}", upload_id, dest.display());
+            add_media_files_blocking(
+                session.album.to_string_lossy().into_owned(),
+                vec![IncomingFile {
+                    name: final_name,
+                    source_path: Some(dest.to_string_lossy().into_owned()),
+                    data: None,
+                }],
+            )?
+            .pop()
+            .ok_or_else(|| "No media registered".to_string())
+        })();
+        let state = match &result {
+            Ok(_) => UploadState::Finished,
+            Err(e) => {
+                log::error!("upload {} failed: {}", upload_id, e);
+                let _ = fs::remove_file(&session.temp);
+                UploadState::Aborted
+            }
+        };
+        emit_upload_progress(&app, &upload_id, &session, state);
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn abort_upload(app: AppHandle<Wry>, upload_id: String) -> Result<(), String> {
+    let mut session = UPLOADS
+        .lock()
+        .unwrap()
+        .remove(&upload_id)
+        .ok_or_else(|| format!("Upload {} not found", upload_id))?;
+    drop(session.file.take());
+    let _ = fs::remove_file(&session.temp);
+    let _ = fs::remove_dir(upload_dir(&session.album));
+    log::info!("aborted upload {} ({})", upload_id, session.name);
+    emit_upload_progress(&app, &upload_id, &session, UploadState::Aborted);
+    Ok(())
+}
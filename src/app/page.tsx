@@ -16,6 +16,7 @@ import {
 } from "@/components/ui/resizable";
 import { Toaster } from "@/components/toaster";
 import { Updater } from "@/components/updater";
+import { useActivityReporter } from "@/lib/hooks/use-activity-reporter";
 import {
   useAlbumWatcher,
   useGalleryController,
@@ -64,6 +65,7 @@ export default function GalleryPage() {
   useAlbumWatcher();
   useMediaWatcher();
   useKeyboardShortcuts();
+  useActivityReporter();
 
   if (!storeReady) {
     return (
@@ -487,22 +487,6 @@ export function AdvancedSettingsPopover({
               }
             />
           </Field>
-          <Field
-            label={t("advanced.field.thumbs.lockPoll")}
-            helper={t("advanced.field.thumbs.lockPoll.helper")}
-            default="50"
-          >
-            <Input
-              type="number"
-              value={settings.thumbnails.lockPollMs}
-              onChange={(e) =>
-                updateField(
-                  ["thumbnails", "lockPollMs"],
-                  Number(e.target.value),
-                )
-              }
-            />
-          </Field>
         </Section>
 
         <Section
@@ -691,6 +675,127 @@ export function AdvancedSettingsPopover({
                 : t("common.off")}
             </Button>
           </Field>
+          <Field
+            label={t("advanced.field.preload.scheduler")}
+            helper={t("advanced.field.preload.scheduler.helper")}
+            default={t("common.enabled")}
+          >
+            <Button
+              size="sm"
+              variant={
+                settings.preload.scheduler.enabled ? "default" : "outline"
+              }
+              onClick={() =>
+                updateField(
+                  ["preload", "scheduler", "enabled"],
+                  !settings.preload.scheduler.enabled,
+                )
+              }
+              className="w-fit"
+            >
+              {settings.preload.scheduler.enabled
+                ? t("common.enabled")
+                : t("common.off")}
+            </Button>
+          </Field>
+          <Field
+            label={t("advanced.field.preload.loadThrottle")}
+            helper={t("advanced.field.preload.loadThrottle.helper")}
+            default="0.8"
+          >
+            <Input
+              type="number"
+              step="0.1"
+              value={settings.preload.scheduler.loadThrottlePerCore}
+              onChange={(e) =>
+                updateField(
+                  ["preload", "scheduler", "loadThrottlePerCore"],
+                  Number(e.target.value),
+                )
+              }
+            />
+          </Field>
+          <Field
+            label={t("advanced.field.preload.loadPause")}
+            helper={t("advanced.field.preload.loadPause.helper")}
+            default="1.5"
+          >
+            <Input
+              type="number"
+              step="0.1"
+              value={settings.preload.scheduler.loadPausePerCore}
+              onChange={(e) =>
+                updateField(
+                  ["preload", "scheduler", "loadPausePerCore"],
+                  Number(e.target.value),
+                )
+              }
+            />
+          </Field>
+          <Field
+            label={t("advanced.field.preload.onBattery")}
+            helper={t("advanced.field.preload.onBattery.helper")}
+            default={t("advanced.battery.throttle")}
+          >
+            <Select
+              value={settings.preload.scheduler.onBattery}
+              onValueChange={(v) =>
+                updateField(["preload", "scheduler", "onBattery"], v)
+              }
+            >
+              <SelectTrigger>
+                {t(`advanced.battery.${settings.preload.scheduler.onBattery}`)}
+              </SelectTrigger>
+              <SelectContent>
+                {(["ignore", "throttle", "pause"] as const).map((policy) => (
+                  <SelectItem key={policy} value={policy}>
+                    {t(`advanced.battery.${policy}`)}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </Field>
+          <Field
+            label={t("advanced.field.preload.throttleActive")}
+            helper={t("advanced.field.preload.throttleActive.helper")}
+            default={t("common.yes")}
+          >
+            <Button
+              size="sm"
+              variant={
+                settings.preload.scheduler.throttleWhileActive
+                  ? "default"
+                  : "outline"
+              }
+              onClick={() =>
+                updateField(
+                  ["preload", "scheduler", "throttleWhileActive"],
+                  !settings.preload.scheduler.throttleWhileActive,
+                )
+              }
+              className="w-fit"
+            >
+              {settings.preload.scheduler.throttleWhileActive
+                ? t("common.yes")
+                : t("common.no")}
+            </Button>
+          </Field>
+          <Field
+            label={t("advanced.field.preload.userIdle")}
+            helper={t("advanced.field.preload.userIdle.helper")}
+            default="30"
+          >
+            <Input
+              type="number"
+              value={settings.preload.scheduler.userIdleSecs}
+              onChange={(e) =>
+                updateField(
+                  ["preload", "scheduler", "userIdleSecs"],
+                  Number(e.target.value),
+                )
+              }
+            />
+          </Field>
         </Section>
 
         <Section
@@ -783,6 +888,31 @@ export function AdvancedSettingsPopover({
                 : t("common.disabled")}
             </Button>
           </Field>
+          <Field
+            label={t("advanced.field.album.collision")}
+            helper={t("advanced.field.album.collision.helper")}
+            default={t("advanced.collision.suffix")}
+          >
+            <Select
+              value={settings.album.collisionPolicy}
+              onValueChange={(v) =>
+                updateField(["album", "collisionPolicy"], v)
+              }
+            >
+              <SelectTrigger>
+                {t(`advanced.collision.${settings.album.collisionPolicy}`)}
+              </SelectTrigger>
+              <SelectContent>
+                {(
+                  ["suffix", "skip", "overwrite-if-identical", "ask"] as const
+                ).map((policy) => (
+                  <SelectItem key={policy} value={policy}>
+                    {t(`advanced.collision.${policy}`)}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </Field>
         </Section>
 
         <div className="text-muted-foreground mt-3 flex items-center gap-1.5 text-xs">
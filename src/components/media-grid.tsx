@@ -23,6 +23,7 @@ import { useStoreWithEqualityFn } from "zustand/traditional";
 import { FavoritesAlbum } from "./favorites-album";
 import { useDragDrop } from "@/lib/hooks/use-drag-drop";
 import { useI18n } from "@/lib/i18n";
+import { requestThumbnails } from "@/lib/fs/albumService";
 
 const nameForClipboard = (file: File, idx: number) => {
   const trimmed = file.name?.trim();
@@ -192,6 +193,40 @@ export default function MediaGrid({
     measureElement: measureWithFallback,
   });
 
+  const visibleItems =
+    layout === "masonry"
+      ? rowVirtualizerMasonry.getVirtualItems()
+      : rowVirtualizerGrid.getVirtualItems();
+  const visibleStart = visibleItems[0]?.index ?? -1;
+  const visibleEnd = visibleItems[visibleItems.length - 1]?.index ?? -1;
+  const requestedThumbsRef = useRef<Set<string>>(new Set());
+
+  useEffect(() => {
+    if (visibleStart < 0) return;
+    const timer = setTimeout(() => {
+      const visible: string[] = [];
+      for (let i = visibleStart; i <= visibleEnd; i++) {
+        if (layout === "masonry") {
+          const p = mediaPaths[i];
+          if (p) visible.push(p);
+        } else {
+          visible.push(...(mediaRows[i] ?? []));
+        }
+      }
+      const current = new Set(visible);
+      const offscreen = [...requestedThumbsRef.current].filter(
+        (p) => !current.has(p),
+      );
+      requestedThumbsRef.current = current;
+      void requestThumbnails(offscreen, "offscreen")
+        .then(() => requestThumbnails(visible, "visible"))
+        .catch((error) => {
+          console.error("Thumbnail request failed", error);
+        });
+    }, 150);
+    return () => clearTimeout(timer);
+  }, [visibleStart, visibleEnd, layout, mediaPaths, mediaRows]);
+
   const scrollerHeight = scrollSize.height ?? scrollTarget?.clientHeight ?? 0;
   const virtualContentHeight =
     layout === "masonry"
@@ -16,6 +16,14 @@ type StageProgress = {
   total: number;
 };
 
+type SchedulerStatus = {
+  mode: "run" | "throttle" | "pause";
+  reasons: string[];
+  load_per_core: number | null;
+  on_battery: boolean | null;
+  user_active: boolean;
+};
+
 type PreloadProgressPayload = {
   stage: StageKey;
   stage_progress: StageProgress;
@@ -26,6 +34,7 @@ type PreloadProgressPayload = {
   thumbnails: StageProgress;
   metadata: StageProgress;
   active_actions: number;
+  scheduler: SchedulerStatus | null;
 };
 
 const emptyStage: StageProgress = { completed: 0, total: 0 };
@@ -43,6 +52,7 @@ const normalizePayload = (
   thumbnails: payload?.thumbnails ?? emptyStage,
   metadata: payload?.metadata ?? emptyStage,
   active_actions: payload?.active_actions ?? 0,
+  scheduler: payload?.scheduler ?? null,
 });
 
 export function PreloadingScreen() {
@@ -223,6 +233,17 @@ export function PreloadingScreen() {
                     {status.overall_completed} / {status.overall_total}
                   </div>
                 </div>
+                {status.scheduler && status.scheduler.mode !== "run" && (
+                  <div className="text-muted-foreground text-xs">
+                    {t(`preload.scheduler.${status.scheduler.mode}`)}
+                    {status.scheduler.reasons.length > 0 &&
+                      `: ${status.scheduler.reasons
+                        .map((reason) =>
+                          t(`preload.scheduler.reason.${reason}`),
+                        )
+                        .join(", ")}`}
+                  </div>
+                )}
               </div>
             </div>
 
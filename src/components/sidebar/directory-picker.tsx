@@ -1,16 +1,46 @@
 import { Button } from "@/components/ui/button";
+import {
+  forgetLibrary,
+  listLibraries,
+  type LibraryInfo,
+} from "@/lib/fs/libraryService";
 import { LottiePlayer } from "@/lib/lottie";
 import { useRootDir } from "@/lib/hooks/use-root-dir";
 import { useRoom237 } from "@/lib/stores";
-import { Suspense } from "react";
-import { IconLoader } from "@tabler/icons-react";
+import { Suspense, useCallback, useEffect, useState } from "react";
+import { IconLoader, IconX } from "@tabler/icons-react";
 import { Settings } from "../settings";
 import { useI18n } from "@/lib/i18n";
 
 export default function DirectoryPicker() {
   const { pickDirectory } = useRootDir();
   const rootDir = useRoom237((state) => state.rootDir);
+  const setRootDir = useRoom237((state) => state.setRootDir);
   const { t } = useI18n();
+  const [libraries, setLibraries] = useState<LibraryInfo[]>([]);
+
+  const refreshLibraries = useCallback(async () => {
+    try {
+      setLibraries(await listLibraries());
+    } catch (e) {
+      console.error(e);
+    }
+  }, []);
+
+  useEffect(() => {
+    if (rootDir) return;
+    void refreshLibraries();
+  }, [rootDir, refreshLibraries]);
+
+  const forget = async (root: string) => {
+    try {
+      await forgetLibrary(root);
+    } catch (e) {
+      console.error(e);
+    }
+    await refreshLibraries();
+  };
+
   if (rootDir) return null;
   return (
     <>
@@ -36,6 +66,44 @@ export default function DirectoryPicker() {
             {t("directory.subtitle")}
           </div>
           <Button onClick={pickDirectory}>{t("directory.choose")}</Button>
+          {libraries.length > 0 && (
+            <div className="mt-6 flex w-full flex-col gap-1 text-left">
+              <div className="text-muted-foreground mb-1 text-xs font-medium">
+                {t("directory.recent")}
+              </div>
+              {libraries.map((library) => (
+                <div
+                  key={library.root}
+                  className="group flex items-center gap-1"
+                >
+                  <Button
+                    variant="ghost"
+                    className="h-auto min-w-0 flex-1 flex-col items-start py-1.5"
+                    disabled={!library.available && !library.cached}
+                    onClick={() => setRootDir(library.root)}
+                  >
+                    <span className="w-full truncate">{library.name}</span>
+                    <span className="text-muted-foreground w-full truncate text-xs font-normal">
+                      {library.available
+                        ? library.root
+                        : library.cached
+                          ? t("directory.offline")
+                          : t("directory.unavailable")}
+                    </span>
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="icon"
+                    className="opacity-0 group-hover:opacity-100"
+                    title={t("directory.forget")}
+                    onClick={() => void forget(library.root)}
+                  >
+                    <IconX className="size-4" />
+                  </Button>
+                </div>
+              ))}
+            </div>
+          )}
         </Suspense>
       </div>
     </>
@@ -2,6 +2,7 @@ import type { FavoriteDetachedMediaEntry, MediaEntry } from "@/lib/types";
 import { invoke } from "@tauri-apps/api/core";
 import path from "path";
 import { exists, mkdir, remove } from "@tauri-apps/plugin-fs";
+import { ask } from "@tauri-apps/plugin-dialog";
 import {
   buildAlbum,
   type Album,
@@ -13,6 +14,7 @@ import { attachMediaEntry } from "../utils";
 import { toast } from "@/components/toaster";
 import { translate } from "@/lib/i18n";
 import type { Language } from "@/lib/stores/types";
+import type { CollisionPolicy } from "@/lib/settings/schema";
 
 const MOVE_MEDIA_BATCH_SIZE = 300;
 
@@ -122,9 +124,11 @@ export function computeAggregatedSizes(
   return totals;
 }
 
-export async function listAlbums(
-  rootDir: string,
-): Promise<{ albumsById: Record<string, Album>; albumTree: AlbumNode[] }> {
+export async function listAlbums(rootDir: string): Promise<{
+  albumsById: Record<string, Album>;
+  albumTree: AlbumNode[];
+  offline: boolean;
+}> {
   const rawAlbums = (await invoke("get_albums_detached", {
     rootDir,
   })) satisfies DetachedAlbum[];
@@ -140,7 +144,8 @@ export async function listAlbums(
     }
   });
   const albumTree = buildAlbumTree(rawAlbums);
-  return { albumsById, albumTree };
+  const offline = rawAlbums.some((album) => album.offline);
+  return { albumsById, albumTree, offline };
 }
 
 export async function markNonDuplicates(
@@ -150,6 +155,16 @@ export async function markNonDuplicates(
   await invoke("mark_non_duplicates", { dir, files });
 }
 
+export type ThumbRequestPriority = "visible" | "offscreen";
+
+export async function requestThumbnails(
+  paths: string[],
+  priority: ThumbRequestPriority,
+): Promise<void> {
+  if (paths.length === 0) return;
+  await invoke("request_thumbnails", { paths, priority });
+}
+
 export async function resetDuplicates(rootDir: string): Promise<void> {
   await invoke("reset_duplicates", { rootDir });
 }
@@ -212,6 +227,41 @@ export async function moveAlbum(
   return res;
 }
 
+export type MoveStatus =
+  | "moved"
+  | "renamed"
+  | "replaced"
+  | "skipped"
+  | "conflict"
+  | "failed";
+
+export type MoveOutcome = {
+  name: string;
+  status: MoveStatus;
+  target?: string;
+  error?: string;
+};
+
+async function moveMediaBatches(
+  source: Album,
+  target: Album,
+  names: string[],
+  collisionPolicy?: CollisionPolicy,
+): Promise<MoveOutcome[]> {
+  const outcomes: MoveOutcome[] = [];
+  for (let i = 0; i < names.length; i += MOVE_MEDIA_BATCH_SIZE) {
+    const chunk = names.slice(i, i + MOVE_MEDIA_BATCH_SIZE);
+    const batch = await invoke<MoveOutcome[]>("move_media_batch", {
+      source: source.path,
+      target: target.path,
+      media: chunk,
+      collisionPolicy,
+    });
+    outcomes.push(...batch);
+  }
+  return outcomes;
+}
+
 export async function moveMedia(
   source: Album,
   target: Album,
@@ -223,28 +273,48 @@ export async function moveMedia(
     translate(language, "toast.moveMedia.loading", { count: medias.length }),
   );
   const names = medias.map((m) => m.name);
-  const failed: string[] = [];
-  for (let i = 0; i < names.length; i += MOVE_MEDIA_BATCH_SIZE) {
-    const chunk = names.slice(i, i + MOVE_MEDIA_BATCH_SIZE);
-    const batchFailed = await invoke<string[]>("move_media_batch", {
-      source: source.path,
-      target: target.path,
-      media: chunk,
+  const outcomes = await moveMediaBatches(source, target, names);
+  const conflicts = outcomes
+    .filter((o) => o.status === "conflict")
+    .map((o) => o.name);
+  if (conflicts.length > 0) {
+    const keepBoth = await ask(
+      translate(language, "moveMedia.conflict.message", {
+        count: conflicts.length,
+      }),
+      {
+        title: translate(language, "moveMedia.conflict.title"),
+        kind: "warning",
+      },
+    );
+    const resolved = await moveMediaBatches(
+      source,
+      target,
+      conflicts,
+      keepBoth ? "suffix" : "skip",
+    );
+    const byName = new Map(resolved.map((o) => [o.name, o]));
+    outcomes.forEach((o, i) => {
+      const next = byName.get(o.name);
+      if (o.status === "conflict" && next) outcomes[i] = next;
     });
-    failed.push(...batchFailed);
   }
+  const failed = outcomes.filter((o) => o.status === "failed");
+  const moved = outcomes.filter((o) =>
+    ["moved", "renamed", "replaced"].includes(o.status),
+  ).length;
   if (failed.length > 0) {
     loadingToast.error(
       translate(language, "toast.moveMedia.failed", { count: failed.length }),
     );
     console.error("Failed to move media files:", failed);
   }
-  if (failed.length < medias.length) {
+  if (moved > 0) {
     loadingToast.success(
-      translate(language, "toast.moveMedia.success", {
-        count: medias.length - failed.length,
-      }),
+      translate(language, "toast.moveMedia.success", { count: moved }),
     );
+  } else if (failed.length === 0) {
+    loadingToast.dismiss();
   }
   albumCache.delete(source.path);
   albumCache.delete(target.path);
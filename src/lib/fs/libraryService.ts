@@ -0,0 +1,28 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  DuplicatesSettings,
+  ThumbnailSettings,
+} from "@/lib/settings/schema";
+
+export type LibraryOverrides = {
+  duplicates?: DuplicatesSettings | null;
+  thumbnails?: ThumbnailSettings | null;
+};
+
+export type LibraryInfo = {
+  root: string;
+  name: string;
+  lastOpened: number | null;
+  available: boolean;
+  cached: boolean;
+  open: boolean;
+  overrides: LibraryOverrides;
+};
+
+export async function listLibraries(): Promise<LibraryInfo[]> {
+  return await invoke<LibraryInfo[]>("list_libraries");
+}
+
+export async function forgetLibrary(rootDir: string): Promise<void> {
+  await invoke("forget_library", { rootDir });
+}
@@ -0,0 +1,25 @@
+"use client";
+
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect } from "react";
+
+const REPORT_INTERVAL_MS = 5000;
+
+export function useActivityReporter() {
+  useEffect(() => {
+    let lastReport = 0;
+    const report = () => {
+      const now = Date.now();
+      if (now - lastReport < REPORT_INTERVAL_MS) return;
+      lastReport = now;
+      void invoke("report_user_activity").catch(console.error);
+    };
+    const events = ["pointermove", "pointerdown", "keydown", "wheel"] as const;
+    events.forEach((event) =>
+      window.addEventListener(event, report, { passive: true }),
+    );
+    return () => {
+      events.forEach((event) => window.removeEventListener(event, report));
+    };
+  }, []);
+}
@@ -3,6 +3,7 @@
 import { useCallback, useEffect, useState } from "react";
 import { open } from "@tauri-apps/plugin-dialog";
 import { exists } from "@tauri-apps/plugin-fs";
+import { listLibraries } from "@/lib/fs/libraryService";
 import { useRoom237 } from "../stores";
 
 export function useRootDir() {
@@ -12,8 +13,9 @@ export function useRootDir() {
 
   const validateRoot = useCallback(async (dir: string | null) => {
     if (!dir) return false;
-    if (!(await exists(dir))) return false;
-    return true;
+    if (await exists(dir)) return true;
+    const libraries = await listLibraries().catch(() => []);
+    return libraries.some((library) => library.root === dir && library.cached);
   }, []);
 
   useEffect(() => {
@@ -45,6 +45,10 @@ export const translations: Record<Language, TranslationDictionary> = {
     "directory.subtitle":
       "All HEIC files in it will be converted to PNG. The metadata for files and albums will appear in the hidden files.",
     "directory.choose": "Choose directory",
+    "directory.recent": "Recent libraries",
+    "directory.unavailable": "Drive not connected",
+    "directory.offline": "Drive not connected, read-only",
+    "directory.forget": "Forget library",
     "lock.title": "FBI locked us out",
     "lock.subtitle":
       "Unfortunately we cannot risk you seeing the media files in this gallery.",
@@ -66,6 +70,11 @@ export const translations: Record<Language, TranslationDictionary> = {
     "preload.conversion": "HEIC to JPEG Conversion",
     "preload.thumbnails": "Thumbnails",
     "preload.metadata": "Metadata",
+    "preload.scheduler.throttle": "Slowed down",
+    "preload.scheduler.pause": "Paused",
+    "preload.scheduler.reason.load": "system is busy",
+    "preload.scheduler.reason.battery": "on battery",
+    "preload.scheduler.reason.user-active": "you are active",
     "layout.grid": "Grid",
     "layout.masonry": "Masonry",
     "layout.apple": "Apple-Style",
@@ -222,9 +231,6 @@ export const translations: Record<Language, TranslationDictionary> = {
     "advanced.field.thumbs.videoSeek": "Video seek (s)",
     "advanced.field.thumbs.videoSeek.helper":
       "Time position for video thumbnails.",
-    "advanced.field.thumbs.lockPoll": "Lock poll (ms)",
-    "advanced.field.thumbs.lockPoll.helper":
-      "Interval to check for thumbnail lock release.",
     "advanced.field.ffmpeg.threads": "Threads",
     "advanced.field.ffmpeg.threads.helper": "Number of threads FFmpeg can use.",
     "advanced.field.ffmpeg.timeout": "Timeout (s)",
@@ -257,6 +263,27 @@ export const translations: Record<Language, TranslationDictionary> = {
     "advanced.field.preload.hashRetry": "Retry on thumb change",
     "advanced.field.preload.hashRetry.helper":
       "Retry computing hash if thumbnail changes during preload.",
+    "advanced.field.preload.scheduler": "Adaptive scheduling",
+    "advanced.field.preload.scheduler.helper":
+      "Slow down or pause preloading based on system load, battery and activity.",
+    "advanced.field.preload.loadThrottle": "Throttle at load per core",
+    "advanced.field.preload.loadThrottle.helper":
+      "One-minute load average per CPU core above which workers slow down.",
+    "advanced.field.preload.loadPause": "Pause at load per core",
+    "advanced.field.preload.loadPause.helper":
+      "One-minute load average per CPU core above which workers pause.",
+    "advanced.field.preload.onBattery": "On battery",
+    "advanced.field.preload.onBattery.helper":
+      "What preloading does while the laptop runs on battery.",
+    "advanced.field.preload.throttleActive": "Throttle while active",
+    "advanced.field.preload.throttleActive.helper":
+      "Slow down preloading while you are using the app.",
+    "advanced.field.preload.userIdle": "Idle after (s)",
+    "advanced.field.preload.userIdle.helper":
+      "Seconds without input before you count as idle.",
+    "advanced.battery.ignore": "Keep going",
+    "advanced.battery.throttle": "Slow down",
+    "advanced.battery.pause": "Pause",
     "advanced.field.metadata.probeTimeout": "Probe timeout (s)",
     "advanced.field.metadata.probeTimeout.helper":
       "Timeout for probing media metadata.",
@@ -269,6 +296,21 @@ export const translations: Record<Language, TranslationDictionary> = {
     "advanced.field.album.moveThumbs": "Move thumbs/meta with media",
     "advanced.field.album.moveThumbs.helper":
       "Move associated thumbnails and metadata.",
+    "advanced.field.album.collision": "Name collisions",
+    "advanced.field.album.collision.helper":
+      "What to do when a moved or imported file name is already taken.",
+    "advanced.collision.suffix": "Add a numeric suffix",
+    "advanced.collision.skip": "Skip the file",
+    "advanced.collision.overwrite-if-identical": "Replace if identical",
+    "advanced.collision.ask": "Ask every time",
+    "moveMedia.conflict.title": "Files already exist",
+    "moveMedia.conflict.message": {
+      one: "{{count}} file already exists in the target album. Keep both copies? Choose No to skip it.",
+      few: "{{count}} files already exist in the target album. Keep both copies? Choose No to skip them.",
+      many: "{{count}} files already exist in the target album. Keep both copies? Choose No to skip them.",
+      other:
+        "{{count}} files already exist in the target album. Keep both copies? Choose No to skip them.",
+    },
   },
   ru: {
     "common.on": "Вкл",
@@ -299,6 +341,10 @@ export const translations: Record<Language, TranslationDictionary> = {
     "directory.subtitle":
       "Все HEIC-файлы в ней будут конвертированы в PNG. Метаданные файлов и альбомов сохранятся в скрытых файлах.",
     "directory.choose": "Выбрать папку",
+    "directory.recent": "Недавние библиотеки",
+    "directory.unavailable": "Диск не подключён",
+    "directory.offline": "Диск не подключён, только просмотр",
+    "directory.forget": "Забыть библиотеку",
     "lock.title": "КГБ закрыл нам доступ",
     "lock.subtitle":
       "К сожалению, мы не можем рисковать показом медиафайлов в этой галерее.",
@@ -320,6 +366,11 @@ export const translations: Record<Language, TranslationDictionary> = {
     "preload.conversion": "Конвертация HEIC в JPEG",
     "preload.thumbnails": "Превью",
     "preload.metadata": "Метаданные",
+    "preload.scheduler.throttle": "Замедлено",
+    "preload.scheduler.pause": "Приостановлено",
+    "preload.scheduler.reason.load": "система загружена",
+    "preload.scheduler.reason.battery": "работа от батареи",
+    "preload.scheduler.reason.user-active": "вы активны",
     "sort.shoot": "Дата EXIF",
     "sort.added": "Дата добавления",
     "sort.name": "Имя",
@@ -474,9 +525,6 @@ export const translations: Record<Language, TranslationDictionary> = {
     "advanced.field.thumbs.videoSeek": "Позиция видео (с)",
     "advanced.field.thumbs.videoSeek.helper":
       "Момент времени для превью видео.",
-    "advanced.field.thumbs.lockPoll": "Период блокировки (мс)",
-    "advanced.field.thumbs.lockPoll.helper":
-      "Интервал проверки снятия блокировки превью.",
     "advanced.field.ffmpeg.threads": "Потоки",
     "advanced.field.ffmpeg.threads.helper":
       "Число потоков, которое может использовать FFmpeg.",
@@ -510,6 +558,27 @@ export const translations: Record<Language, TranslationDictionary> = {
     "advanced.field.preload.hashRetry": "Повтор при изменении превью",
     "advanced.field.preload.hashRetry.helper":
       "Повторять подсчёт хэша, если превью изменилось во время предзагрузки.",
+    "advanced.field.preload.scheduler": "Адаптивное планирование",
+    "advanced.field.preload.scheduler.helper":
+      "Замедлять или приостанавливать предзагрузку в зависимости от нагрузки, батареи и активности.",
+    "advanced.field.preload.loadThrottle": "Замедлять при нагрузке на ядро",
+    "advanced.field.preload.loadThrottle.helper":
+      "Средняя нагрузка за минуту на ядро, выше которой потоки замедляются.",
+    "advanced.field.preload.loadPause": "Пауза при нагрузке на ядро",
+    "advanced.field.preload.loadPause.helper":
+      "Средняя нагрузка за минуту на ядро, выше которой потоки приостанавливаются.",
+    "advanced.field.preload.onBattery": "От батареи",
+    "advanced.field.preload.onBattery.helper":
+      "Что делать с предзагрузкой, когда ноутбук работает от батареи.",
+    "advanced.field.preload.throttleActive": "Замедлять при активности",
+    "advanced.field.preload.throttleActive.helper":
+      "Замедлять предзагрузку, пока вы пользуетесь приложением.",
+    "advanced.field.preload.userIdle": "Бездействие через (с)",
+    "advanced.field.preload.userIdle.helper":
+      "Через сколько секунд без ввода вы считаетесь неактивным.",
+    "advanced.battery.ignore": "Продолжать",
+    "advanced.battery.throttle": "Замедлять",
+    "advanced.battery.pause": "Пауза",
     "advanced.field.metadata.probeTimeout": "Таймаут опроса (с)",
     "advanced.field.metadata.probeTimeout.helper":
       "Таймаут извлечения метаданных файла.",
@@ -523,5 +592,20 @@ export const translations: Record<Language, TranslationDictionary> = {
     "advanced.field.album.moveThumbs": "Перенос превью/метаданных с медиа",
     "advanced.field.album.moveThumbs.helper":
       "Переносить связанные превью и метаданные.",
+    "advanced.field.album.collision": "Совпадение имён",
+    "advanced.field.album.collision.helper":
+      "Что делать, если имя перемещаемого или импортируемого файла уже занято.",
+    "advanced.collision.suffix": "Добавить числовой суффикс",
+    "advanced.collision.skip": "Пропустить файл",
+    "advanced.collision.overwrite-if-identical": "Заменить, если идентичен",
+    "advanced.collision.ask": "Спрашивать каждый раз",
+    "moveMedia.conflict.title": "Файлы уже существуют",
+    "moveMedia.conflict.message": {
+      one: "{{count}} файл уже есть в целевом альбоме. Сохранить обе копии? «Нет» — пропустить.",
+      few: "{{count}} файла уже есть в целевом альбоме. Сохранить обе копии? «Нет» — пропустить.",
+      many: "{{count}} файлов уже есть в целевом альбоме. Сохранить обе копии? «Нет» — пропустить.",
+      other:
+        "{{count}} файла уже есть в целевом альбоме. Сохранить обе копии? «Нет» — пропустить.",
+    },
   },
 };
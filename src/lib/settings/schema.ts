@@ -6,6 +6,22 @@ export type HashSize = "8x8" | "16x16" | "32x32";
 export type HashAlgorithm = "blockhash" | "phash" | "dhash";
 export type ResizeFilter = "nearest" | "triangle" | "catmullrom" | "lanczos3";
 export type ThreadSetting = number | "auto";
+export type CollisionPolicy =
+  | "suffix"
+  | "skip"
+  | "overwrite-if-identical"
+  | "ask";
+export type BatteryPolicy = "ignore" | "throttle" | "pause";
+
+export type SchedulerPolicy = {
+  enabled: boolean;
+  loadThrottlePerCore: number;
+  loadPausePerCore: number;
+  onBattery: BatteryPolicy;
+  throttleWhileActive: boolean;
+  userIdleSecs: number;
+  throttleDelayMs: number;
+};
 
 export type DuplicatesSettings = {
   threshold: number;
@@ -21,7 +37,6 @@ export type ThumbnailSettings = {
   imageWebpQuality: number;
   imageWebpCompressionLevel: number;
   videoSeekSeconds: number;
-  lockPollMs: number;
 };
 
 export type FfmpegSettings = {
@@ -39,6 +54,7 @@ export type PreloadSettings = {
   thumbHashQueueDelayMs: number;
   thumbHashOnlyAfterIdle: boolean;
   thumbHashRetryOnThumbChange: boolean;
+  scheduler: SchedulerPolicy;
 };
 
 export type MetadataSettings = {
@@ -49,12 +65,22 @@ export type MetadataSettings = {
 export type AlbumSettings = {
   renameCleanupDelaySecs: number;
   moveRenameThumbsAndMeta: boolean;
+  collisionPolicy: CollisionPolicy;
+};
+
+export type PrivacyExportProfile = {
+  stripLocation: boolean;
+  stripSerialNumbers: boolean;
+  stripOwner: boolean;
+  stripXmp: boolean;
+  stripAll: boolean;
 };
 
 export type PrivacySettings = {
   enabled: boolean;
   lockscreenEnabled: boolean;
   confirmOpenEnabled: boolean;
+  exportProfile: PrivacyExportProfile;
 };
 
 export type AdvancedSettings = {
@@ -81,7 +107,6 @@ export const defaultAdvancedSettings: AdvancedSettings = {
     imageWebpQuality: 75,
     imageWebpCompressionLevel: 3,
     videoSeekSeconds: 1,
-    lockPollMs: 50,
   },
   ffmpeg: {
     threads: 4,
@@ -97,6 +122,15 @@ export const defaultAdvancedSettings: AdvancedSettings = {
     thumbHashQueueDelayMs: 10,
     thumbHashOnlyAfterIdle: true,
     thumbHashRetryOnThumbChange: true,
+    scheduler: {
+      enabled: true,
+      loadThrottlePerCore: 0.8,
+      loadPausePerCore: 1.5,
+      onBattery: "throttle",
+      throttleWhileActive: true,
+      userIdleSecs: 30,
+      throttleDelayMs: 250,
+    },
   },
   metadata: {
     ffmpegProbeTimeoutSecs: 5,
@@ -105,11 +139,19 @@ export const defaultAdvancedSettings: AdvancedSettings = {
   album: {
     renameCleanupDelaySecs: 1,
     moveRenameThumbsAndMeta: true,
+    collisionPolicy: "suffix",
   },
   privacy: {
     enabled: false,
     lockscreenEnabled: false,
     confirmOpenEnabled: false,
+    exportProfile: {
+      stripLocation: true,
+      stripSerialNumbers: true,
+      stripOwner: true,
+      stripXmp: true,
+      stripAll: false,
+    },
   },
 };
 
@@ -148,7 +190,14 @@ export function clampAdvancedSettings(
       ...incoming?.thumbnails,
     },
     ffmpeg: { ...defaultAdvancedSettings.ffmpeg, ...incoming?.ffmpeg },
-    preload: { ...defaultAdvancedSettings.preload, ...incoming?.preload },
+    preload: {
+      ...defaultAdvancedSettings.preload,
+      ...incoming?.preload,
+      scheduler: {
+        ...defaultAdvancedSettings.preload.scheduler,
+        ...incoming?.preload?.scheduler,
+      },
+    },
     metadata: { ...defaultAdvancedSettings.metadata, ...incoming?.metadata },
     album: { ...defaultAdvancedSettings.album, ...incoming?.album },
     privacy: { ...defaultAdvancedSettings.privacy, ...incoming?.privacy },
@@ -217,14 +266,6 @@ export function clampAdvancedSettings(
     0,
     30,
   );
-  merged.thumbnails.lockPollMs = clamp(
-    coerceNumber(
-      merged.thumbnails.lockPollMs,
-      defaultAdvancedSettings.thumbnails.lockPollMs,
-    ),
-    5,
-    250,
-  );
 
   merged.ffmpeg.threads = clampThreads(merged.ffmpeg.threads);
   merged.ffmpeg.timeoutSecs = clamp(
@@ -286,6 +327,44 @@ export function clampAdvancedSettings(
     0,
     100,
   );
+  const scheduler = merged.preload.scheduler;
+  scheduler.enabled = Boolean(scheduler.enabled);
+  scheduler.throttleWhileActive = Boolean(scheduler.throttleWhileActive);
+  scheduler.loadThrottlePerCore = clamp(
+    coerceNumber(
+      scheduler.loadThrottlePerCore,
+      defaultAdvancedSettings.preload.scheduler.loadThrottlePerCore,
+    ),
+    0.1,
+    8,
+  );
+  scheduler.loadPausePerCore = clamp(
+    coerceNumber(
+      scheduler.loadPausePerCore,
+      defaultAdvancedSettings.preload.scheduler.loadPausePerCore,
+    ),
+    scheduler.loadThrottlePerCore,
+    16,
+  );
+  scheduler.userIdleSecs = clamp(
+    coerceNumber(
+      scheduler.userIdleSecs,
+      defaultAdvancedSettings.preload.scheduler.userIdleSecs,
+    ),
+    5,
+    600,
+  );
+  scheduler.throttleDelayMs = clamp(
+    coerceNumber(
+      scheduler.throttleDelayMs,
+      defaultAdvancedSettings.preload.scheduler.throttleDelayMs,
+    ),
+    10,
+    5000,
+  );
+  if (!["ignore", "throttle", "pause"].includes(scheduler.onBattery)) {
+    scheduler.onBattery = defaultAdvancedSettings.preload.scheduler.onBattery;
+  }
 
   merged.metadata.ffmpegProbeTimeoutSecs = clamp(
     coerceNumber(
@@ -304,12 +383,30 @@ export function clampAdvancedSettings(
     0,
     10,
   );
+  if (
+    !["suffix", "skip", "overwrite-if-identical", "ask"].includes(
+      merged.album.collisionPolicy,
+    )
+  ) {
+    merged.album.collisionPolicy = defaultAdvancedSettings.album.collisionPolicy;
+  }
 
   merged.privacy.enabled = Boolean(merged.privacy.enabled);
   merged.privacy.lockscreenEnabled = Boolean(merged.privacy.lockscreenEnabled);
   merged.privacy.confirmOpenEnabled = Boolean(
     merged.privacy.confirmOpenEnabled,
   );
+  const exportProfile = {
+    ...defaultAdvancedSettings.privacy.exportProfile,
+    ...merged.privacy.exportProfile,
+  };
+  merged.privacy.exportProfile = {
+    stripLocation: Boolean(exportProfile.stripLocation),
+    stripSerialNumbers: Boolean(exportProfile.stripSerialNumbers),
+    stripOwner: Boolean(exportProfile.stripOwner),
+    stripXmp: Boolean(exportProfile.stripXmp),
+    stripAll: Boolean(exportProfile.stripAll),
+  };
 
   return merged;
 }
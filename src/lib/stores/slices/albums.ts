@@ -306,7 +306,7 @@ export const albumsSlice: CustomStateCreator<AlbumsSlice> = (set, get) => ({
       state.displayDecoy && state.decoyRoot ? state.decoyRoot : state.rootDir;
     if (!rootDir) return;
     const loadingToast = toast.loading("Loading albums...");
-    const { albumsById, albumTree } = await listAlbums(rootDir);
+    const { albumsById, albumTree, offline } = await listAlbums(rootDir);
     const validPaths = new Set(
       Object.values(albumsById).map((album) => album.path),
     );
@@ -327,6 +327,9 @@ export const albumsSlice: CustomStateCreator<AlbumsSlice> = (set, get) => ({
       ),
     }));
     loadingToast.dismiss();
+    if (offline) {
+      toast.info("Library drive is offline, showing cached albums read-only");
+    }
     set({ albumsReady: true });
     const activeAlbumId = get().activeAlbumId;
     if (activeAlbumId) {
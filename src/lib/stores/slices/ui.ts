@@ -7,8 +7,6 @@ import {
 import type { DetachedMediaEntry, MediaEntry } from "@/lib/types";
 import type { Album } from "@/lib/types/album";
 import { attachMediaEntry, isMedia } from "@/lib/utils";
-import { join, tempDir } from "@tauri-apps/api/path";
-import { remove, writeFile } from "@tauri-apps/plugin-fs";
 import { invoke } from "@tauri-apps/api/core";
 import { translate } from "@/lib/i18n";
 import type { CustomStateCreator, UISlice } from "../types";
@@ -19,6 +17,37 @@ type FileWithPath = File & { path?: string };
 const hasFilePath = (file: File): file is FileWithPath =>
   "path" in file && typeof (file as FileWithPath).path === "string";
 
+const UPLOAD_CHUNK_SIZE = 8 * 1024 * 1024;
+
+const uploadFile = async (
+  albumPath: string,
+  file: File,
+  name: string,
+): Promise<DetachedMediaEntry> => {
+  const uploadId = await invoke<string>("begin_upload", {
+    albumPath,
+    name,
+    size: file.size,
+  });
+  try {
+    for (let offset = 0; offset < file.size; offset += UPLOAD_CHUNK_SIZE) {
+      const chunk = new Uint8Array(
+        await file.slice(offset, offset + UPLOAD_CHUNK_SIZE).arrayBuffer(),
+      );
+      await invoke<number>("append_upload_chunk", chunk, {
+        headers: {
+          "upload-id": uploadId,
+          "upload-offset": String(offset),
+        },
+      });
+    }
+    return await invoke<DetachedMediaEntry>("finish_upload", { uploadId });
+  } catch (error) {
+    await invoke("abort_upload", { uploadId }).catch(() => undefined);
+    throw error;
+  }
+};
+
 const guessExtFromType = (type: string): string => {
   if (!type) return "bin";
   if (type === "image/jpeg") return "jpg";
@@ -459,32 +488,22 @@ export const uiSlice: CustomStateCreator<UISlice> = (set, get) => ({
       }),
     );
 
-    const tempFiles: string[] = [];
-
     set({ batchOperationInProgress: true });
     try {
-      const pasteTempDir = await tempDir();
-      const now = Date.now();
-      const payload = await Promise.all(
-        mediaFiles.map(async ({ file, name }, idx) => {
-          const sourcePath = hasFilePath(file) ? file.path : undefined;
-          if (!sourcePath) {
-            const buffer = await file.arrayBuffer();
-            const bytes = new Uint8Array(buffer);
-            const tmpName = `room237-paste-${now}-${idx}-${name}`;
-            const tmpPath = await join(pasteTempDir, tmpName);
-            await writeFile(tmpPath, bytes);
-            tempFiles.push(tmpPath);
-            return { name, sourcePath: tmpPath };
-          }
-          return { name, sourcePath };
-        }),
+      const withPath = mediaFiles.flatMap(({ file, name }) =>
+        hasFilePath(file) ? [{ name, sourcePath: file.path }] : [],
       );
+      const inMemory = mediaFiles.filter(({ file }) => !hasFilePath(file));
 
-      const added = await invoke<DetachedMediaEntry[]>("add_media_files", {
-        dir: album.path,
-        files: payload,
-      });
+      const added = withPath.length
+        ? await invoke<DetachedMediaEntry[]>("add_media_files", {
+            dir: album.path,
+            files: withPath,
+          })
+        : [];
+      for (const { file, name } of inMemory) {
+        added.push(await uploadFile(album.path, file, name));
+      }
 
       if (added.length > 0) {
         const attached = added.map((entry) =>
@@ -523,13 +542,6 @@ export const uiSlice: CustomStateCreator<UISlice> = (set, get) => ({
           : translate(lang, "toast.failedAdd");
       loadingToast.error(message);
     } finally {
-      for (const path of tempFiles) {
-        try {
-          await remove(path);
-        } catch (err) {
-          console.warn("Failed to cleanup temp paste file", err);
-        }
-      }
       set({ batchOperationInProgress: false });
     }
   },
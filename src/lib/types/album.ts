@@ -13,6 +13,13 @@ export interface DetachedAlbum {
   size: number;
   relative_path: string;
   parent?: string | null;
+  title?: string;
+  description?: string;
+  cover?: string;
+  sort_key?: SortKey;
+  sort_dir?: SortDir;
+  hidden?: boolean;
+  offline?: boolean;
 }
 
 export type AlbumNode = {
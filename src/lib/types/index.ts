@@ -23,6 +23,7 @@ export interface DetachedMediaEntry {
   meta: string;
   name: string;
   favorite?: boolean;
+  thumb?: string;
 }
 
 export interface FavoriteDetachedMediaEntry extends DetachedMediaEntry {
@@ -226,7 +226,8 @@ export function attachMediaEntry(
   return {
     url: convertFileSrc(path.join(albumPath, entry.name)),
     thumb: convertFileSrc(
-      path.join(albumPath, ".room237-thumb", `${entry.name}.webp`),
+      entry.thumb ??
+        path.join(albumPath, ".room237-thumb", `${entry.name}.webp`),
     ),
     meta: unpackFileMeta(entry.meta),
     name: entry.name,